@@ -1,4 +1,10 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Declare `has_protoc` as a known cfg regardless of which branch below
+    // sets it, so `#[cfg(has_protoc)]`/`#[cfg(not(has_protoc))]` in
+    // src/grpc/mod.rs don't trip rustc's `unexpected_cfgs` lint (a hard
+    // error under `-D warnings`) when it's unset.
+    println!("cargo:rustc-check-cfg=cfg(has_protoc)");
+
     // Check if protoc is available
     match std::process::Command::new("protoc").arg("--version").output() {
         Ok(_) => {
@@ -7,7 +13,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .build_server(true)
                 .build_client(true)
                 .compile(&["proto/agent.proto"], &["proto"])?;
-            
+
+            // Gates the real, codegen-backed gRPC service (including the
+            // streaming `Chat` RPC) in src/grpc/mod.rs; without protoc we
+            // fall back to the manual, non-streaming placeholder types.
+            println!("cargo:rustc-cfg=has_protoc");
             println!("cargo:rerun-if-changed=proto/agent.proto");
             println!("cargo:rerun-if-changed=proto");
         }
@@ -1,16 +1,131 @@
-use reqwest::Client;
-use serde_json::json;
+use futures::StreamExt;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
 use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Load the same shared secret the server reads via `AuthConfig::from_env`
+/// (the `AUTH_SECRET` env var, or the file named by `AUTH_SECRET_FILE`), so
+/// this client attaches the bearer token the server expects. Returns `None`
+/// if neither is set, matching auth being disabled server-side.
+fn load_auth_secret() -> Option<String> {
+    if let Ok(secret) = std::env::var("AUTH_SECRET") {
+        return Some(secret);
+    }
+    let path = std::env::var("AUTH_SECRET_FILE").ok()?;
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Attach the shared secret as a bearer token, if one is configured; `/health`
+/// (and the static playground pages, which this example doesn't fetch) is the
+/// only endpoint left open, so every other request in this example goes through
+/// this helper
+fn with_auth(builder: RequestBuilder, secret: &Option<String>) -> RequestBuilder {
+    match secret {
+        Some(secret) => builder.bearer_auth(secret),
+        None => builder,
+    }
+}
+
+/// Errors specific to the `/tasks/stream` duplex protocol, distinct from a
+/// bare `reqwest::Error` since a truncated or malformed frame isn't itself a
+/// transport failure
+#[derive(Debug)]
+enum TaskStreamError {
+    /// The connection closed before a complete frame was read
+    EarlyEof,
+    /// A frame didn't parse as JSON, or reading the response body failed
+    Protocol(String),
+}
+
+impl std::fmt::Display for TaskStreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskStreamError::EarlyEof => write!(f, "connection closed before a complete frame arrived"),
+            TaskStreamError::Protocol(msg) => write!(f, "protocol error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for TaskStreamError {}
+
+/// Client side of `/tasks/stream`'s duplex newline-delimited JSON channel,
+/// modeled on a CI runner: the request body is fed from a channel (so the
+/// connection stays open for further frames instead of closing after
+/// `task_start`) while the response body is read back as its own stream of
+/// frames, independent of how the request body is progressing.
+struct TaskStreamClient {
+    outbound: mpsc::Sender<Result<Vec<u8>, std::io::Error>>,
+    inbound: reqwest::Response,
+    buffer: String,
+}
+
+impl TaskStreamClient {
+    /// Open the duplex connection and send `task_start` as the first frame
+    async fn connect(
+        client: &Client,
+        base_url: &str,
+        auth_secret: &Option<String>,
+        agent_id: Option<String>,
+        input: String,
+    ) -> Result<Self, reqwest::Error> {
+        let (tx, rx) = mpsc::channel::<Result<Vec<u8>, std::io::Error>>(16);
+        let body = reqwest::Body::wrap_stream(ReceiverStream::new(rx));
+
+        let response = with_auth(client.post(&format!("{}/tasks/stream", base_url)), auth_secret)
+            .body(body)
+            .send()
+            .await?;
+
+        let mut task = Self { outbound: tx, inbound: response, buffer: String::new() };
+        task.send(&json!({ "kind": "task_start", "agent_id": agent_id, "input": input })).await;
+        Ok(task)
+    }
+
+    /// Write one JSON value as a newline-terminated frame to the request body
+    async fn send(&self, message: &Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        let _ = self.outbound.send(Ok(line.into_bytes())).await;
+    }
+
+    /// Read the next newline-delimited JSON frame from the response body,
+    /// buffering partial reads until a full line is available. `Ok(None)`
+    /// once the connection closes cleanly with nothing left buffered.
+    async fn recv(&mut self) -> Result<Option<Value>, TaskStreamError> {
+        loop {
+            if let Some(pos) = self.buffer.find('\n') {
+                let line: String = self.buffer.drain(..=pos).collect();
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                return serde_json::from_str(line)
+                    .map(Some)
+                    .map_err(|e| TaskStreamError::Protocol(e.to_string()));
+            }
+
+            match self.inbound.chunk().await {
+                Ok(Some(chunk)) => self.buffer.push_str(&String::from_utf8_lossy(&chunk)),
+                Ok(None) if self.buffer.trim().is_empty() => return Ok(None),
+                Ok(None) => return Err(TaskStreamError::EarlyEof),
+                Err(e) => return Err(TaskStreamError::Protocol(e.to_string())),
+            }
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     println!("🔌 Helixor Client Test");
     println!("Testing deployed agent via HTTP API");
     println!("");
-    
+
     let client = Client::new();
     let base_url = "http://localhost:8080";
-    
+    let auth_secret = load_auth_secret();
+
     // Test health endpoint
     println!("🏥 Testing health endpoint...");
     match client.get(&format!("{}/health", base_url))
@@ -39,7 +154,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     
     // Test status endpoint
     println!("📊 Testing status endpoint...");
-    match client.get(&format!("{}/status", base_url))
+    match with_auth(client.get(&format!("{}/status", base_url)), &auth_secret)
         .timeout(Duration::from_secs(5))
         .send()
         .await {
@@ -69,7 +184,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         "session_id": "test-session-123"
     });
     
-    match client.post(&format!("{}/chat", base_url))
+    match with_auth(client.post(&format!("{}/chat", base_url)), &auth_secret)
         .header("Content-Type", "application/json")
         .json(&chat_request)
         .timeout(Duration::from_secs(30))
@@ -94,6 +209,119 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         }
     }
     
+    println!("");
+
+    // Test streaming chat endpoint: request an SSE stream and print deltas as
+    // they arrive instead of waiting for the full buffered response
+    println!("📡 Testing streaming chat endpoint...");
+    let stream_request = json!({
+        "message": "Count from 1 to 5, one number per sentence.",
+        "session_id": "test-session-stream",
+        "stream": true
+    });
+
+    match with_auth(client.post(&format!("{}/chat", base_url)), &auth_secret)
+        .header("Accept", "text/event-stream")
+        .json(&stream_request)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await {
+        Ok(response) => {
+            if response.status().is_success() {
+                print!("   Deltas: ");
+                let mut buffer = String::new();
+                let mut byte_stream = response.bytes_stream();
+                'frames: while let Some(chunk) = byte_stream.next().await {
+                    let chunk = match chunk {
+                        Ok(chunk) => chunk,
+                        Err(e) => {
+                            println!("\n❌ Stream read failed: {}", e);
+                            break;
+                        }
+                    };
+                    buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                    while let Some(pos) = buffer.find("\n\n") {
+                        let frame: String = buffer.drain(..pos + 2).collect();
+                        let Some(data) = frame.trim_end().strip_prefix("data:") else { continue };
+                        let data = data.trim();
+
+                        if data == "[DONE]" {
+                            println!();
+                            println!("✅ Stream complete!");
+                            break 'frames;
+                        }
+
+                        if let Ok(frame_json) = serde_json::from_str::<serde_json::Value>(data) {
+                            if let Some(delta) = frame_json["delta"].as_str() {
+                                print!("{}", delta);
+                            }
+                        }
+                    }
+                }
+            } else {
+                println!("❌ Streaming chat request failed with status: {}", response.status());
+            }
+        }
+        Err(e) => {
+            println!("❌ Streaming chat request failed: {}", e);
+        }
+    }
+
+    println!("");
+
+    // Test the long-lived task-stream channel: start a task, print each step
+    // frame as it streams back, then upload bytes for the artifact it announces
+    println!("🛠️  Testing task stream endpoint...");
+    match TaskStreamClient::connect(
+        &client,
+        base_url,
+        &auth_secret,
+        None,
+        "Write a two-sentence changelog entry for a release.".to_string(),
+    )
+    .await
+    {
+        Ok(mut task) => loop {
+            match task.recv().await {
+                Ok(Some(frame)) => match frame["kind"].as_str() {
+                    Some("step") => print!("{}", frame["output"].as_str().unwrap_or_default()),
+                    Some("artifact_create") => {
+                        println!();
+                        let object_id = frame["object_id"].as_str().unwrap_or_default();
+                        println!("   📦 Artifact announced: {} ({})", frame["name"], frame["description"]);
+                        let upload = with_auth(
+                            client.post(&format!("{}/artifacts/{}", base_url, object_id)),
+                            &auth_secret,
+                        )
+                        .body("(placeholder artifact bytes)")
+                        .send()
+                        .await;
+                        match upload {
+                            Ok(resp) if resp.status().is_success() => println!("   ✅ Artifact uploaded"),
+                            Ok(resp) => println!("   ❌ Artifact upload failed with status: {}", resp.status()),
+                            Err(e) => println!("   ❌ Artifact upload failed: {}", e),
+                        }
+                    }
+                    Some("task_done") => {
+                        println!("✅ Task stream complete!");
+                        break;
+                    }
+                    _ => {}
+                },
+                Ok(None) => {
+                    println!("\n❌ Task stream closed before task_done");
+                    break;
+                }
+                Err(e) => {
+                    println!("\n❌ Task stream error: {}", e);
+                    break;
+                }
+            }
+        },
+        Err(e) => println!("❌ Failed to open task stream: {}", e),
+    }
+
     println!("");
     println!("🎉 Client test completed!");
     println!("");
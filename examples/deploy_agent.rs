@@ -10,6 +10,8 @@ use helixor::{
 
 #[tokio::main]
 async fn main() -> HelixorResult<()> {
+    helixor::logging::init_tracing(false);
+
     println!("🚀 Starting Helixor Agent Deployment Example");
     
     // Get API key from environment
@@ -1,18 +1,45 @@
-use crate::model::{model_trait::Message, Model};
+pub mod context;
+
+use crate::errchan::ErrChan;
+use crate::error::HelixorError;
 use crate::memory::memory_trait::Memory;
+use crate::model::{model_trait::Message, Model, ToolCallRequest, ToolSchema};
 use crate::tool::tool_traits::Tool;
+
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
 use serde::Deserialize;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::instrument;
 
+/// Agent handles tool invocation, memory storage, and LLM communication.
 pub struct Agent {
     pub name: String,
     pub instructions: String,
     pub model: Box<dyn Model + Send + Sync>,
     pub tools: Vec<Box<dyn Tool + Send + Sync>>,
     pub memory: Arc<dyn Memory + Send + Sync>,
+    /// When set, tool failures (e.g. an unrecognized tool name) are reported here
+    /// instead of only being printed/logged inline
+    pub err_chan: Option<ErrChan>,
+    /// Ceiling on tool-calling round-trips within a single `run_once`, guarding
+    /// against a model that never stops calling tools. Defaults to `DEFAULT_MAX_TOOL_STEPS`.
+    pub max_tool_steps: usize,
+    /// Wall-clock budget for a single tool call; `None` (the default) means no timeout.
+    /// A call that times out is reported back to the model as a `HelixorError::ToolFailure`
+    /// rather than hanging the whole step.
+    pub step_timeout: Option<Duration>,
 }
 
 impl Agent {
+    /// Default ceiling on tool-calling round-trips within a single `run_once`
+    pub const DEFAULT_MAX_TOOL_STEPS: usize = 8;
+
+    /// How many tool calls from the same model turn run at once
+    const TOOL_CONCURRENCY: usize = 4;
+
+    /// Create a new agent with model, tools, and memory
     pub fn new(
         name: String,
         instructions: String,
@@ -26,55 +53,177 @@ impl Agent {
             model,
             tools,
             memory,
+            err_chan: None,
+            max_tool_steps: Self::DEFAULT_MAX_TOOL_STEPS,
+            step_timeout: None,
+        }
+    }
+
+    /// Process a single user input: generate a response, print it as it streams in,
+    /// use tools, and store everything in memory
+    #[instrument(skip(self, input), fields(agent_name = %self.name))]
+    pub async fn run(&mut self, input: &str) {
+        println!("\n🟦 Input: {input}");
+
+        let messages = self.build_messages(input).await;
+
+        // Store user message
+        let _ = self.memory.store("user", input).await;
+
+        // Stream the model's response, printing fragments as they arrive
+        let mut stream = self.model.generate_stream(messages).await;
+        let mut response = String::new();
+        while let Some(fragment) = stream.next().await {
+            print!("{fragment}");
+            response.push_str(&fragment);
+        }
+        println!();
+
+        // Store assistant response
+        let _ = self.memory.store("assistant", &response).await;
+
+        // Try to parse tool usage
+        match Self::parse_tool_call(&response) {
+            Some(tool_call) => {
+                println!("🛠 Tool Call: {}({})", tool_call.name, tool_call.args);
+                self.invoke_tool(tool_call).await;
+            }
+            None => println!("💬 Agent replied without tool usage."),
         }
+
+        println!("✅ Agent finished.");
     }
 
-    pub async fn run(&self, input: &str) {
-        println!("🤖 {} received input: {input}", self.name);
+    /// Process a single user input, running a reason-act loop: the model may call a
+    /// tool, see its result, and call another tool (or answer) based on it, up to
+    /// `max_tool_steps` times before the loop is forced to stop. Tool calls are
+    /// requested through the provider's native function-calling API (see
+    /// `tool_schemas`) rather than scraped from the reply text. Returns the final
+    /// natural-language answer; see `run_once_with_steps` for a variant that also
+    /// reports how many rounds it took.
+    #[instrument(skip(self, input), fields(agent_name = %self.name))]
+    pub async fn run_once(&mut self, input: &str) -> String {
+        self.run_once_with_steps(input).await.0
+    }
+
+    /// Same as `run_once`, but also returns the number of model round-trips taken
+    /// (1 if the model answered without calling any tool), so a caller driving a
+    /// multi-agent workflow (e.g. `TeamDispatcher`'s FSM/DAG executors) can log how
+    /// much reasoning a step actually took.
+    #[instrument(skip(self, input), fields(agent_name = %self.name))]
+    pub async fn run_once_with_steps(&mut self, input: &str) -> (String, usize) {
+        let mut messages = self.build_messages(input).await;
+        let _ = self.memory.store("user", input).await;
+
+        let schemas = self.tool_schemas();
+        let mut response = String::new();
+        let mut steps_taken = 0;
 
-        // Load history from memory
+        for step in 0..self.max_tool_steps {
+            tracing::debug!(step, "tool-calling round");
+            let reply = self.model.generate_with_tools(messages.clone(), &schemas).await;
+            response = reply.content.clone().unwrap_or_default();
+            steps_taken = step + 1;
+
+            if reply.tool_calls.is_empty() {
+                break;
+            }
+
+            // Record the assistant's reply text and the tool calls it made (as
+            // `name(arguments)` entries, since `Message` carries only role and
+            // content), then each tool's result, so the model can see what it did
+            // and reason about the output on the next turn
+            let calls: Vec<String> = reply
+                .tool_calls
+                .iter()
+                .map(|call| format!("{}({})", call.name, call.arguments))
+                .collect();
+            messages.push(Message {
+                role: "assistant".to_string(),
+                content: format!("{}\n[tool calls: {}]", response, calls.join(", ")).trim_start().to_string(),
+            });
+
+            for tool_message in self.invoke_tool_calls(reply.tool_calls).await {
+                messages.push(tool_message);
+            }
+        }
+
+        let _ = self.memory.store("assistant", &response).await;
+        (response, steps_taken)
+    }
+
+    /// Process a single user input like `run`, but hand back the model's response
+    /// as a stream of text fragments instead of printing them, so a caller (e.g. a
+    /// WebSocket handler) can forward each fragment to a client as it arrives. The
+    /// full response is still accumulated and stored to memory once the stream is
+    /// exhausted.
+    #[instrument(skip(self, input), fields(agent_name = %self.name))]
+    pub async fn run_stream(&mut self, input: &str) -> BoxStream<'static, String> {
+        let messages = self.build_messages(input).await;
+        let _ = self.memory.store("user", input).await;
+
+        let memory = self.memory.clone();
+        let accumulated = Arc::new(Mutex::new(String::new()));
+        let collector = accumulated.clone();
+
+        let fragments = self
+            .model
+            .generate_stream(messages)
+            .await
+            .inspect(move |fragment| collector.lock().unwrap().push_str(fragment));
+
+        // A trailing empty fragment that stores the accumulated response once the
+        // model's stream is exhausted; filtered out so callers only ever see real
+        // content.
+        let flush = stream::once(async move {
+            let response = accumulated.lock().unwrap().clone();
+            let _ = memory.store("assistant", &response).await;
+            String::new()
+        });
+
+        Box::pin(
+            fragments
+                .chain(flush)
+                .filter(|fragment| futures::future::ready(!fragment.is_empty())),
+        )
+    }
+
+    /// JSON-Schema descriptions of every tool this agent has available, for sending
+    /// to providers that support native function-calling
+    fn tool_schemas(&self) -> Vec<ToolSchema> {
+        self.tools
+            .iter()
+            .map(|tool| ToolSchema {
+                name: tool.name().to_string(),
+                description: format!("The '{}' tool.", tool.name()),
+                parameters: tool.schema(),
+            })
+            .collect()
+    }
+
+    /// Build the message history sent to the model: system instructions, memory, then
+    /// the new user input
+    async fn build_messages(&self, input: &str) -> Vec<Message> {
         let mut messages = self.memory.load().await;
 
-        // Add system instructions
         messages.insert(0, Message {
             role: "system".to_string(),
             content: self.instructions.clone(),
         });
 
-        // Add user input to messages
         messages.push(Message {
             role: "user".to_string(),
             content: input.to_string(),
         });
 
-        // Store user message
-        self.memory.store("user", input).await;
-
-        // Generate response from model
-        let response = self.model.generate(messages.clone()).await;
-        println!("🧠 Model says: {response}");
-
-        // Store assistant response
-        self.memory.store("assistant", &response).await;
-
-        // Parse and invoke tool if applicable
-        if let Some(tool_call) = Self::parse_tool_call(&response) {
-            println!("🛠 Tool call detected: {}({})", tool_call.name, tool_call.args);
-            self.invoke_tool(tool_call).await;
-        } else {
-            println!("💬 Normal assistant reply.");
-        }
-
-        println!("✅ Agent finished.");
+        messages
     }
 
-    pub async fn run_loop(&self) {
+    /// REPL loop for continuous interaction
+    pub async fn run_loop(&mut self) {
         use std::io::{self, Write};
 
-        println!(
-            "🤖 Agent '{}' is ready. Type your message or 'exit' to quit.",
-            self.name
-        );
+        println!("\n🤖 Agent '{}' is ready. Type input or 'exit' to quit.", self.name);
 
         loop {
             print!("> ");
@@ -82,70 +231,222 @@ impl Agent {
 
             let mut input = String::new();
             if io::stdin().read_line(&mut input).is_err() {
-                println!("❌ Failed to read input.");
+                println!("❌ Error reading input.");
                 continue;
             }
 
-            let trimmed = input.trim();
-            if trimmed.eq_ignore_ascii_case("exit") {
-                println!("👋 Goodbye!");
-                break;
-            }
-
-            if trimmed == "/memory" {
-                let history = self.memory.load().await;
-                println!("🧠 Memory:");
-                for msg in history {
-                    println!("[{}] {}", msg.role, msg.content);
+            let input = input.trim();
+            match input {
+                "exit" => {
+                    println!("👋 Goodbye.");
+                    break;
                 }
-                continue;
-            }
-
-            if trimmed == "/clear" {
-                match self.memory.clear().await {
-                    Ok(_) => println!("🧹 Memory cleared."),
-                    Err(e) => println!("❌ Could not clear memory: {}", e),
+                "/memory" => {
+                    let history = self.memory.load().await;
+                    println!("🧠 Memory:");
+                    for msg in history {
+                        println!("{}: {}", msg.role, msg.content);
+                    }
+                }
+                "/clear" => {
+                    if let Err(e) = self.memory.clear().await {
+                        println!("❌ Could not clear memory: {}", e);
+                    } else {
+                        println!("🧹 Memory cleared.");
+                    }
+                }
+                _ => {
+                    self.run(input).await;
                 }
-                continue;
             }
-
-            self.run(trimmed).await;
         }
     }
 
-    async fn invoke_tool(&self, call: ToolCall) {
-        let tool = self.tools.iter().find(|t| t.name() == call.name);
+    /// Extract tool call from model response (if any)
+    fn parse_tool_call(response: &str) -> Option<ToolCall> {
+        serde_json::from_str::<ToolCallWrapper>(response)
+            .ok()
+            .map(|wrapper| wrapper.tool_call)
+    }
 
-        match tool {
+    /// Invoke the appropriate tool with arguments and log output to memory
+    #[instrument(skip(self, call), fields(agent_name = %self.name, tool = %call.name))]
+    async fn invoke_tool(&mut self, call: ToolCall) {
+        match self.tools.iter().find(|t| t.name() == call.name) {
             Some(tool) => {
-                let output = tool.call(&call.args).await;
-                println!("🔧 Tool [{}] says: {output}", tool.name());
-
-                self.memory
+                let output = match tool.call(&call.args).await {
+                    Ok(output) => output,
+                    Err(e) => {
+                        tracing::warn!(tool = %call.name, error = %e, "tool call failed");
+                        if let Some(err_chan) = &self.err_chan {
+                            err_chan.send(e.to_string(), "tool").await;
+                        }
+                        format!("❌ {}", e)
+                    }
+                };
+                println!("🔧 Tool [{}]: {}", tool.name(), output);
+                let _ = self
+                    .memory
                     .store("tool", &format!("{} → {}", tool.name(), output))
                     .await;
             }
             None => {
-                println!("⚠️ Tool '{}' not found.", call.name);
-                self.memory
+                tracing::warn!(tool = %call.name, "tool not found");
+                if let Some(err_chan) = &self.err_chan {
+                    err_chan.send(format!("unknown tool: {}", call.name), "tool").await;
+                }
+                let _ = self
+                    .memory
                     .store("assistant", &format!("⚠️ Unknown tool: {}", call.name))
                     .await;
             }
         }
     }
 
-    fn parse_tool_call(response: &str) -> Option<ToolCall> {
-        serde_json::from_str::<ToolCallWrapper>(response)
-            .ok()
-            .map(|wrapper| wrapper.tool_call)
+    /// Run a whole model turn's worth of tool calls, up to `TOOL_CONCURRENCY` at a
+    /// time (they're independent requests from the same turn, so there's no reason
+    /// to serialize them), each bounded by `step_timeout` if set, and return one
+    /// "tool" `Message` per call in the order the model requested them so the next
+    /// turn sees a stable transcript regardless of which call finished first.
+    async fn invoke_tool_calls(&self, calls: Vec<ToolCallRequest>) -> Vec<Message> {
+        let timeout = self.step_timeout;
+        let mut outputs: Vec<(usize, String)> = stream::iter(calls.into_iter().enumerate())
+            .map(|(index, call)| async move {
+                let output = match self.tools.iter().find(|t| t.name() == call.name) {
+                    Some(tool) => {
+                        let result = match timeout {
+                            Some(duration) => tokio::time::timeout(duration, tool.call(&call.arguments))
+                                .await
+                                .unwrap_or_else(|_| {
+                                    Err(HelixorError::ToolFailure {
+                                        tool: tool.name().to_string(),
+                                        msg: format!("timed out after {:?}", duration),
+                                    })
+                                }),
+                            None => tool.call(&call.arguments).await,
+                        };
+                        match result {
+                            Ok(output) => output,
+                            Err(e) => {
+                                tracing::warn!(tool = %call.name, error = %e, "tool call failed");
+                                if let Some(err_chan) = &self.err_chan {
+                                    err_chan.send(e.to_string(), "tool").await;
+                                }
+                                format!("❌ {}", e)
+                            }
+                        }
+                    }
+                    None => {
+                        tracing::warn!(tool = %call.name, "tool not found");
+                        if let Some(err_chan) = &self.err_chan {
+                            err_chan.send(format!("unknown tool: {}", call.name), "tool").await;
+                        }
+                        format!("⚠️ Unknown tool: {}", call.name)
+                    }
+                };
+                let _ = self.memory.store("tool", &format!("{} → {}", call.name, output)).await;
+                (index, output)
+            })
+            .buffer_unordered(Self::TOOL_CONCURRENCY)
+            .collect()
+            .await;
+
+        outputs.sort_by_key(|(index, _)| *index);
+        outputs
+            .into_iter()
+            .map(|(_, content)| Message { role: "tool".to_string(), content })
+            .collect()
+    }
+}
+
+/// Builder for assembling an `Agent` from its parts, mirroring `TeamBuilder`'s
+/// fluent style
+pub struct AgentBuilder {
+    name: String,
+    instructions: String,
+    model: Option<Box<dyn Model + Send + Sync>>,
+    tools: Vec<Box<dyn Tool + Send + Sync>>,
+    memory: Option<Arc<dyn Memory + Send + Sync>>,
+    err_chan: Option<ErrChan>,
+    max_tool_steps: usize,
+    step_timeout: Option<Duration>,
+}
+
+impl AgentBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            instructions: String::new(),
+            model: None,
+            tools: Vec::new(),
+            memory: None,
+            err_chan: None,
+            max_tool_steps: Agent::DEFAULT_MAX_TOOL_STEPS,
+            step_timeout: None,
+        }
+    }
+
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = instructions.into();
+        self
+    }
+
+    pub fn with_model(mut self, model: Box<dyn Model + Send + Sync>) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_tools(mut self, tools: Vec<Box<dyn Tool + Send + Sync>>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    pub fn with_memory(mut self, memory: Arc<dyn Memory + Send + Sync>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Report tool failures to a central `ErrChan` instead of only logging them inline
+    pub fn with_err_chan(mut self, err_chan: ErrChan) -> Self {
+        self.err_chan = Some(err_chan);
+        self
+    }
+
+    /// Override the default (`Agent::DEFAULT_MAX_TOOL_STEPS`) ceiling on tool-calling
+    /// round-trips within a single `run_once`
+    pub fn with_max_tool_steps(mut self, max_tool_steps: usize) -> Self {
+        self.max_tool_steps = max_tool_steps.max(1);
+        self
+    }
+
+    /// Bound how long a single tool call may run before it's reported back to the
+    /// model as a failed call instead of blocking the rest of the step
+    pub fn with_step_timeout(mut self, step_timeout: Duration) -> Self {
+        self.step_timeout = Some(step_timeout);
+        self
+    }
+
+    pub fn build(self) -> Agent {
+        Agent {
+            name: self.name,
+            instructions: self.instructions,
+            model: self.model.expect("AgentBuilder requires with_model(...)"),
+            tools: self.tools,
+            memory: self.memory.expect("AgentBuilder requires with_memory(...)"),
+            err_chan: self.err_chan,
+            max_tool_steps: self.max_tool_steps,
+            step_timeout: self.step_timeout,
+        }
     }
 }
 
+/// Wrapper struct for parsing tool calls
 #[derive(Debug, Deserialize)]
 struct ToolCallWrapper {
     tool_call: ToolCall,
 }
 
+/// Struct representing a tool call
 #[derive(Debug, Deserialize)]
 struct ToolCall {
     name: String,
@@ -0,0 +1,5 @@
+pub mod runner;
+pub mod workload;
+
+pub use runner::{run_workload_file, run_workloads, write_report, Percentiles, TaskResult, WorkloadResult};
+pub use workload::WorkloadFile;
@@ -0,0 +1,128 @@
+use crate::bench::workload::WorkloadFile;
+use crate::team::{Team, TeamDispatcher, TeamWorkflow};
+use serde::Serialize;
+use std::time::Instant;
+
+/// p50/p95 over a set of samples gathered across a task's iterations
+#[derive(Debug, Clone, Serialize)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p95: f64,
+}
+
+fn percentiles(mut values: Vec<f64>) -> Percentiles {
+    if values.is_empty() {
+        return Percentiles { p50: 0.0, p95: 0.0 };
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let pick = |p: f64| {
+        let idx = ((values.len() as f64 - 1.0) * p).round() as usize;
+        values[idx.min(values.len() - 1)]
+    };
+    Percentiles { p50: pick(0.50), p95: pick(0.95) }
+}
+
+/// One task's results across all of its iterations
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskResult {
+    pub task: String,
+    pub iterations: usize,
+    pub latency_ms: Percentiles,
+    /// Blank-line-separated segments in the workflow's output (one per agent
+    /// turn/state/node), used as a proxy for per-agent step count since
+    /// `TeamDispatcher` doesn't separately expose a step counter
+    pub step_count: Percentiles,
+    /// Whitespace word count of the task plus the workflow's output, as a
+    /// cheap stand-in for a real token count - `Agent`/`Model` don't
+    /// currently report provider token usage
+    pub approx_tokens: Percentiles,
+}
+
+/// Results for one workload file, covering every task it listed
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadResult {
+    pub workload_file: String,
+    pub team_config: String,
+    pub workflow: String,
+    pub tasks: Vec<TaskResult>,
+}
+
+fn workflow_kind_name(workflow: &TeamWorkflow) -> &'static str {
+    match workflow {
+        TeamWorkflow::RoundRobin => "round_robin",
+        TeamWorkflow::ChainOfThought => "chain_of_thought",
+        TeamWorkflow::Parallel => "parallel",
+        TeamWorkflow::FSM { .. } => "fsm",
+        TeamWorkflow::DAG { .. } => "dag",
+    }
+}
+
+/// Load `path`, build its team once, and run every task `iterations` times
+/// through the same `TeamDispatcher`, recording latency/step/token
+/// percentiles per task.
+pub async fn run_workload_file(path: &str) -> Result<WorkloadResult, Box<dyn std::error::Error>> {
+    let workload = WorkloadFile::load(path)?;
+    let team_content = std::fs::read_to_string(&workload.team_config)?;
+    let team: Team = toml::from_str(&team_content)?;
+    let workflow = workflow_kind_name(&team.workflow).to_string();
+    let mut dispatcher = TeamDispatcher::new(team).await?;
+
+    let iterations = workload.iterations.max(1);
+    let mut tasks = Vec::new();
+
+    for task in &workload.tasks {
+        let mut latencies = Vec::with_capacity(iterations);
+        let mut step_counts = Vec::with_capacity(iterations);
+        let mut approx_tokens = Vec::with_capacity(iterations);
+
+        for iteration in 0..iterations {
+            tracing::info!(workload = %path, task, iteration = iteration + 1, iterations, "running benchmark iteration");
+
+            let started = Instant::now();
+            let output = dispatcher.execute(task).await?;
+            let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+            latencies.push(elapsed_ms);
+            step_counts.push(output.split("\n\n").filter(|s| !s.is_empty()).count() as f64);
+            approx_tokens.push((task.split_whitespace().count() + output.split_whitespace().count()) as f64);
+        }
+
+        tasks.push(TaskResult {
+            task: task.clone(),
+            iterations,
+            latency_ms: percentiles(latencies),
+            step_count: percentiles(step_counts),
+            approx_tokens: percentiles(approx_tokens),
+        });
+    }
+
+    Ok(WorkloadResult {
+        workload_file: path.to_string(),
+        team_config: workload.team_config,
+        workflow,
+        tasks,
+    })
+}
+
+/// Run every workload file in turn, logging (rather than aborting on) any
+/// that fails to load or execute, so one bad file doesn't sink the rest of
+/// an invocation covering several
+pub async fn run_workloads(paths: &[String]) -> Vec<WorkloadResult> {
+    let mut results = Vec::new();
+    for path in paths {
+        match run_workload_file(path).await {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::warn!(workload = %path, error = %e, "benchmark workload failed"),
+        }
+    }
+    results
+}
+
+/// Write the combined results of one or more workload runs to `path` as
+/// pretty-printed JSON, so reports can be diffed across commits or posted to
+/// a results server
+pub fn write_report(path: &str, results: &[WorkloadResult]) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(results)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
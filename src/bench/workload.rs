@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+/// One benchmark run: a team config to load, the tasks to put through it, and
+/// how many times to repeat each task so latency/step/token figures can be
+/// reported as percentiles instead of single noisy samples.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkloadFile {
+    pub team_config: String,
+    pub tasks: Vec<String>,
+    #[serde(default = "default_iterations")]
+    pub iterations: usize,
+}
+
+fn default_iterations() -> usize {
+    1
+}
+
+impl WorkloadFile {
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
@@ -1,6 +1,7 @@
 use clap::{Parser, Subcommand};
 use crate::deploy;
 use crate::deploy::server;
+use crate::team::{Team, TeamDispatcher};
 
 /// CLI entrypoint for Helixor/AEGNO
 #[derive(Parser)]
@@ -17,12 +18,19 @@ pub enum Commands {
         /// Path to the agent config TOML file
         #[arg(short, long, default_value = "agent.toml")]
         config: String,
+        /// Name of a persona declared in the config's `[roles]` table to start
+        /// the agent as, instead of its default instructions
+        #[arg(short, long)]
+        role: Option<String>,
     },
     /// Run a team of agents using a configuration file
     Team {
         /// Path to the team config TOML file
         #[arg(short, long, default_value = "team.toml")]
         config: String,
+        /// Task to hand the team; prompted for on stdin if omitted
+        #[arg(short, long)]
+        task: Option<String>,
     },
     /// Deploy an agent or team to Docker and get a URL
     Deploy {
@@ -42,6 +50,14 @@ pub enum Commands {
         #[arg(long, default_value = "latest")]
         tag: String,
     },
+    /// Run one or more benchmark workload files against their team configs
+    Bench {
+        /// JSON workload files, each describing a team config, tasks, and iteration count
+        workloads: Vec<String>,
+        /// Write the combined JSON report to this path
+        #[arg(short, long)]
+        output: Option<String>,
+    },
     /// Start HTTP/gRPC server for deployed agent
     Serve {
         /// HTTP port to serve on (default: 8080)
@@ -61,21 +77,61 @@ pub async fn run_cli() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Run { config } => {
+        Commands::Run { config, role } => {
             println!("🧠 Running agent with config: {}", config);
+            if let Some(role) = &role {
+                println!("🎭 Persona: {}", role);
+            }
 
-            // TODO: Integrate actual agent loading here
-            // For example:
-            // let agent = load_agent(&config).await?;
-            // agent.run_loop().await;
-
-            // Placeholder only
+            match server::load_agent_from_config(&config, role.as_deref()).await {
+                Ok(mut agent) => agent.run_loop().await,
+                Err(e) => eprintln!("❌ Failed to load agent config '{}': {}", config, e),
+            }
         }
-        Commands::Team { config } => {
+        Commands::Team { config, task } => {
             println!("🤝 Running team with config: {}", config);
 
-            // TODO: Integrate actual team loading here
-            // Placeholder only
+            match load_team_config(&config) {
+                Ok(team) => {
+                    let task = task.unwrap_or_else(prompt_for_task);
+                    match TeamDispatcher::new(team).await {
+                        Ok(mut dispatcher) => match dispatcher.execute(&task).await {
+                            Ok(result) => println!("\n{}", result),
+                            Err(e) => eprintln!("❌ Team execution failed: {}", e),
+                        },
+                        Err(e) => eprintln!("❌ Failed to initialize team: {}", e),
+                    }
+                }
+                Err(e) => eprintln!("❌ Failed to load team config '{}': {}", config, e),
+            }
+        }
+        Commands::Bench { workloads, output } => {
+            println!("📊 Running {} benchmark workload file(s)", workloads.len());
+            let results = crate::bench::run_workloads(&workloads).await;
+
+            for result in &results {
+                println!("\n== {} ({}) ==", result.workload_file, result.workflow);
+                for task in &result.tasks {
+                    println!(
+                        "  \"{}\" x{}: latency_ms p50={:.1} p95={:.1} | steps p50={:.1} p95={:.1} | tokens(approx) p50={:.1} p95={:.1}",
+                        task.task,
+                        task.iterations,
+                        task.latency_ms.p50,
+                        task.latency_ms.p95,
+                        task.step_count.p50,
+                        task.step_count.p95,
+                        task.approx_tokens.p50,
+                        task.approx_tokens.p95,
+                    );
+                }
+            }
+
+            if let Some(output) = output {
+                match crate::bench::write_report(&output, &results) {
+                    Ok(()) => println!("\n📝 Report written to {}", output),
+                    Err(e) => eprintln!("❌ Failed to write report to '{}': {}", output, e),
+                }
+            }
         }
         Commands::Deploy { config, port, grpc_port, name, tag } => {
             println!("🚀 Deploying with config: {}", config);
@@ -103,3 +159,25 @@ pub async fn run_cli() {
         }
     }
 }
+
+/// Load a `Team` definition from a team config TOML file
+fn load_team_config(path: &str) -> Result<Team, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let team: Team = toml::from_str(&content)?;
+    Ok(team)
+}
+
+/// Prompt the user on stdin for a task when none was given on the command line
+fn prompt_for_task() -> String {
+    use std::io::{self, Write};
+
+    print!("📝 Task for the team: ");
+    io::stdout().flush().unwrap();
+
+    let mut task = String::new();
+    if io::stdin().read_line(&mut task).is_err() {
+        eprintln!("❌ Error reading input.");
+    }
+
+    task.trim().to_string()
+}
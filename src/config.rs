@@ -1,12 +1,51 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 
+use crate::deploy::tls::TlsConfig;
+use crate::model::ProviderConfig;
+use crate::role::Role;
+
 #[derive(Debug, Deserialize)]
 pub struct AgentConfig {
     pub name: String,
+    /// A `provider:model` identifier (e.g. `openai:gpt-4-turbo`) resolved via
+    /// `providers` through a `ModelRegistry`
     pub model: String,
     pub tools: Vec<String>,
     pub instructions: String,
+    /// Named model providers this agent can select from via its `model` field
+    #[serde(default)]
+    pub providers: HashMap<String, ProviderConfig>,
+    /// Named personas this agent can be started as, selected by name (e.g. via
+    /// the CLI `--role` flag) instead of always using `instructions` verbatim
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
+    /// Enables HTTPS/TLS-terminated gRPC for this agent's deployed servers when
+    /// present; plaintext otherwise
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Extra ways to reach this agent alongside the primary HTTP/gRPC servers
+    #[serde(default)]
+    pub gateways: GatewayConfig,
+}
+
+/// Declares which of the non-HTTP `Gateway` implementations `start_server_from_config`
+/// should also start. All are disabled unless named here.
+#[derive(Debug, Default, Deserialize)]
+pub struct GatewayConfig {
+    /// Run a stdin/stdout REPL (`ConsoleGateway`) alongside the servers
+    #[serde(default)]
+    pub console: bool,
+    /// Path to bind a plain newline-delimited chat `UnixSocketGateway` on
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// Path to bind a `JsonRpcGateway` over a Unix socket on
+    #[serde(default)]
+    pub json_rpc_socket: Option<String>,
+    /// Port to bind a `JsonRpcGateway` over HTTP on
+    #[serde(default)]
+    pub json_rpc_http_port: Option<u16>,
 }
 
 pub fn load_agent_config(path: &str) -> Result<AgentConfig, Box<dyn std::error::Error>> {
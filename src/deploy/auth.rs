@@ -0,0 +1,50 @@
+/// Shared-secret bearer token guarding a deployed agent's HTTP API endpoints (all
+/// but `/health` and the static `/` and `/arena` pages). Loaded once at startup via `AuthConfig::from_env`; absent, the
+/// server falls back to no auth, same as `TlsConfig` falls back to plaintext.
+#[derive(Clone)]
+pub struct AuthConfig {
+    secret: String,
+}
+
+impl AuthConfig {
+    /// Read the shared secret from the `AUTH_SECRET` env var, or, if unset, from
+    /// the file named by `AUTH_SECRET_FILE` (trimmed of trailing whitespace so a
+    /// trailing newline from `echo` or a mounted Kubernetes secret doesn't break
+    /// the comparison). Returns `None` if neither is set, meaning auth is disabled.
+    pub fn from_env() -> Option<Self> {
+        if let Ok(secret) = std::env::var("AUTH_SECRET") {
+            return Some(Self { secret });
+        }
+
+        let path = std::env::var("AUTH_SECRET_FILE").ok()?;
+        let secret = std::fs::read_to_string(path).ok()?.trim().to_string();
+        Some(Self { secret })
+    }
+
+    /// Whether `authorization_header` (the raw `Authorization` header value, if
+    /// present) carries this config's secret as a `Bearer` token. Compared in
+    /// constant time so a public ingress can't use response-timing
+    /// differences to learn how many leading bytes of the token it guessed
+    /// correctly.
+    pub fn accepts(&self, authorization_header: Option<&str>) -> bool {
+        authorization_header
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.secret.as_bytes()))
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first
+/// mismatch, so the running time depends only on `a.len()`/`b.len()`, not on
+/// where (or whether) the two inputs first differ. A length mismatch is
+/// still reported up front - revealing a token's length isn't the leak this
+/// guards against, a timing side channel on its *content* is.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
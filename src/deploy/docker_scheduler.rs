@@ -0,0 +1,206 @@
+use bollard::container::{Config as ContainerConfig, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions};
+use bollard::service::{HostConfig, PortBinding};
+use bollard::Docker;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+
+/// One Docker host the scheduler can place containers on
+#[derive(Debug, Clone, Deserialize)]
+pub struct DockerEndpointConfig {
+    pub uri: String,
+    pub max_concurrent: usize,
+    /// Docker API versions this endpoint is allowed to serve; empty means unconstrained
+    #[serde(default)]
+    pub required_docker_api_versions: Vec<String>,
+}
+
+impl DockerEndpointConfig {
+    /// Reads a JSON array of endpoint configs from `HELIXOR_DOCKER_ENDPOINTS`;
+    /// falls back to a single unconstrained local daemon, matching the
+    /// single-host behavior `deploy_agent` had before the scheduler existed
+    pub fn from_env_or_default() -> Vec<Self> {
+        std::env::var("HELIXOR_DOCKER_ENDPOINTS")
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_else(|| {
+                vec![Self {
+                    uri: "unix:///var/run/docker.sock".to_string(),
+                    max_concurrent: 8,
+                    required_docker_api_versions: Vec::new(),
+                }]
+            })
+    }
+}
+
+/// A connected endpoint plus the bookkeeping the scheduler needs to pick it
+struct Endpoint {
+    uri: String,
+    docker: Docker,
+    semaphore: Arc<Semaphore>,
+    enabled: bool,
+}
+
+/// Where a container returned by `run_container` ended up, so callers can
+/// route `stop_container`/later lookups to the right host
+#[derive(Debug, Clone)]
+pub struct DeploymentHandle {
+    pub container_id: String,
+    pub container_name: String,
+    pub endpoint_uri: String,
+}
+
+/// Spreads container placements across a fleet of Docker hosts instead of
+/// only localhost, picking whichever connected endpoint has the most free
+/// capacity (tracked via a semaphore seeded with `max_concurrent` permits)
+pub struct DockerScheduler {
+    endpoints: Vec<Endpoint>,
+    placements: Arc<Mutex<HashMap<String, String>>>,
+    /// Holds the semaphore permit `run_container` claimed for each container
+    /// it placed, keyed by container name, for as long as the container is
+    /// actually running. Dropping (removing) an entry here - done by
+    /// `stop_container` once the container is confirmed stopped and removed
+    /// - is what returns the permit to its endpoint, so `max_concurrent`
+    /// throttles concurrently *running* containers rather than just
+    /// concurrent calls to `run_container`.
+    running: Arc<Mutex<HashMap<String, OwnedSemaphorePermit>>>,
+}
+
+impl DockerScheduler {
+    /// Connect to every configured endpoint and disable any whose reported
+    /// API version isn't in `required_docker_api_versions` (an empty list
+    /// means the endpoint is accepted regardless of version)
+    pub async fn connect(configs: Vec<DockerEndpointConfig>) -> Result<Self, bollard::errors::Error> {
+        let mut endpoints = Vec::with_capacity(configs.len());
+
+        for config in configs {
+            let docker = if config.uri.starts_with("unix://") {
+                Docker::connect_with_unix(&config.uri, 120, bollard::API_DEFAULT_VERSION)?
+            } else {
+                Docker::connect_with_http(&config.uri, 120, bollard::API_DEFAULT_VERSION)?
+            };
+
+            let mut enabled = true;
+            if !config.required_docker_api_versions.is_empty() {
+                let version = docker.version().await?;
+                let reported = version.api_version.unwrap_or_default();
+                if !config.required_docker_api_versions.contains(&reported) {
+                    tracing::warn!(uri = %config.uri, api_version = %reported, "disabling Docker endpoint: API version not in required set");
+                    enabled = false;
+                }
+            }
+
+            endpoints.push(Endpoint {
+                uri: config.uri,
+                docker,
+                semaphore: Arc::new(Semaphore::new(config.max_concurrent)),
+                enabled,
+            });
+        }
+
+        Ok(Self { endpoints, placements: Arc::new(Mutex::new(HashMap::new())), running: Arc::new(Mutex::new(HashMap::new())) })
+    }
+
+    /// Claim a permit on the enabled endpoint with the most free capacity.
+    /// Tries endpoints most-free-first with `try_acquire`, so two concurrent
+    /// callers can't both pick the same endpoint based on a stale permit
+    /// count and have the loser block on a busy host - the permit is staked
+    /// out atomically as part of the pick, not after.
+    fn pick_endpoint(&self) -> Option<(&Endpoint, OwnedSemaphorePermit)> {
+        let mut candidates: Vec<&Endpoint> = self.endpoints.iter().filter(|e| e.enabled).collect();
+        candidates.sort_by_key(|e| std::cmp::Reverse(e.semaphore.available_permits()));
+
+        candidates.into_iter().find_map(|e| e.semaphore.clone().try_acquire_owned().ok().map(|permit| (e, permit)))
+    }
+
+    /// Create and start a container on whichever endpoint currently has the
+    /// most free capacity, mapping host `port` to container port 8080
+    pub async fn run_container(
+        &self,
+        name: &str,
+        image: &str,
+        port: u16,
+    ) -> Result<DeploymentHandle, Box<dyn std::error::Error + Send + Sync>> {
+        let (endpoint, permit) = self.pick_endpoint().ok_or("no Docker endpoint has free capacity")?;
+
+        let mut port_bindings = HashMap::new();
+        port_bindings.insert(
+            "8080/tcp".to_string(),
+            Some(vec![PortBinding { host_ip: None, host_port: Some(port.to_string()) }]),
+        );
+
+        let container_config = ContainerConfig {
+            image: Some(image.to_string()),
+            env: Some(vec!["RUST_LOG=info".to_string()]),
+            host_config: Some(HostConfig {
+                port_bindings: Some(port_bindings),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let created = endpoint
+            .docker
+            .create_container(Some(CreateContainerOptions { name, platform: None }), container_config)
+            .await?;
+
+        endpoint.docker.start_container::<String>(name, None).await?;
+
+        self.placements.lock().await.insert(name.to_string(), endpoint.uri.clone());
+        // Held until `stop_container` confirms the container is actually
+        // gone, not dropped at the end of this function - see `running`'s
+        // doc comment.
+        self.running.lock().await.insert(name.to_string(), permit);
+
+        Ok(DeploymentHandle {
+            container_id: created.id,
+            container_name: name.to_string(),
+            endpoint_uri: endpoint.uri.clone(),
+        })
+    }
+
+    /// Stop and remove a container this scheduler placed, using its recorded
+    /// endpoint, then release the permit `run_container` claimed for it so
+    /// the endpoint's free capacity reflects the container actually being gone
+    pub async fn stop_container(&self, name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let uri = self.placements.lock().await.remove(name).ok_or("no known placement for container")?;
+        self.stop_container_on(&uri, name).await?;
+        self.running.lock().await.remove(name);
+        Ok(())
+    }
+
+    /// Stop and remove a container on a specific endpoint, bypassing the
+    /// placements table. Used when the endpoint is already known (e.g. the
+    /// `endpoint_uri` recorded on a `DeploymentHandle` from a prior process).
+    pub async fn stop_container_on(&self, uri: &str, name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let endpoint = self.endpoints.iter().find(|e| e.uri == uri).ok_or("no such Docker endpoint is configured")?;
+        endpoint.docker.stop_container(name, None).await?;
+        endpoint.docker.remove_container(name, None::<RemoveContainerOptions>).await?;
+        Ok(())
+    }
+
+    /// List deployed agent containers across every enabled endpoint, skipping
+    /// (with a warning) any endpoint that fails to respond rather than
+    /// failing the whole listing
+    pub async fn list_containers(&self) -> Vec<String> {
+        let mut names = Vec::new();
+
+        for endpoint in self.endpoints.iter().filter(|e| e.enabled) {
+            let mut filters = HashMap::new();
+            filters.insert("ancestor".to_string(), vec!["helixor".to_string()]);
+
+            let options = ListContainersOptions { all: true, filters, ..Default::default() };
+            match endpoint.docker.list_containers(Some(options)).await {
+                Ok(containers) => {
+                    names.extend(containers.into_iter().flat_map(|c| c.names.unwrap_or_default()).map(|n| n.trim_start_matches('/').to_string()));
+                }
+                Err(e) => {
+                    tracing::warn!(uri = %endpoint.uri, error = %e, "failed to list containers on Docker endpoint");
+                }
+            }
+        }
+
+        names
+    }
+}
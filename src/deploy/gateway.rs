@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tokio::sync::Mutex;
+
+use crate::agent::Agent;
+use crate::deploy::server::{AgentRegistry, DEFAULT_AGENT_ID};
+use crate::deploy::tls::TlsConfig;
+
+/// A transport that can drive an `AgentRegistry` until its listen loop ends or
+/// errors. Lets the same agents be reached over HTTP, a terminal, a raw socket,
+/// or JSON-RPC without any of those call sites changing.
+#[async_trait]
+pub trait Gateway: Send + Sync {
+    async fn listen(&self, registry: AgentRegistry) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Serves the existing HTTP routes (`/health`, `/chat`, `/chat/stream`, `/status`,
+/// `/agents`) over the full registry
+pub struct HttpGateway {
+    pub port: u16,
+    pub tls: Option<TlsConfig>,
+}
+
+#[async_trait]
+impl Gateway for HttpGateway {
+    async fn listen(&self, registry: AgentRegistry) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        crate::deploy::server::start_server(registry, self.port, self.tls.clone()).await
+    }
+}
+
+/// Drives the default agent through its stdin/stdout REPL
+pub struct ConsoleGateway;
+
+#[async_trait]
+impl Gateway for ConsoleGateway {
+    async fn listen(&self, registry: AgentRegistry) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let agent = registry
+            .get(DEFAULT_AGENT_ID)
+            .await
+            .ok_or("no default agent registered for the console gateway")?;
+        agent.lock().await.run_loop().await;
+        Ok(())
+    }
+}
+
+/// Accepts newline-delimited chat messages over a Unix domain socket, replying
+/// with the default agent's plain-text response on the same connection
+pub struct UnixSocketGateway {
+    pub path: String,
+}
+
+#[async_trait]
+impl Gateway for UnixSocketGateway {
+    async fn listen(&self, registry: AgentRegistry) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let agent = registry
+            .get(DEFAULT_AGENT_ID)
+            .await
+            .ok_or("no default agent registered for the unix socket gateway")?;
+        let _ = std::fs::remove_file(&self.path);
+        let listener = UnixListener::bind(&self.path)?;
+        tracing::info!(path = %self.path, "listening on unix socket");
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let agent = agent.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let response = agent.lock().await.run_once(&line).await;
+                    if writer.write_all(format!("{response}\n").as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Where a `JsonRpcGateway` accepts connections
+pub enum JsonRpcTransport {
+    UnixSocket(String),
+    Http(u16),
+}
+
+/// Speaks JSON-RPC 2.0: a single `chat` method taking `{message, session_id}`
+/// params and returning `{response, session_id}` as its `result`, or a proper
+/// `{error: {code, message}}` on a bad request or unknown method
+pub struct JsonRpcGateway {
+    pub transport: JsonRpcTransport,
+}
+
+#[async_trait]
+impl Gateway for JsonRpcGateway {
+    async fn listen(&self, registry: AgentRegistry) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let agent = registry
+            .get(DEFAULT_AGENT_ID)
+            .await
+            .ok_or("no default agent registered for the JSON-RPC gateway")?;
+        match &self.transport {
+            JsonRpcTransport::UnixSocket(path) => Self::listen_socket(path, agent).await,
+            JsonRpcTransport::Http(port) => Self::listen_http(*port, agent).await,
+        }
+    }
+}
+
+impl JsonRpcGateway {
+    async fn listen_socket(path: &str, agent: Arc<Mutex<Agent>>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path)?;
+        tracing::info!(path = %path, "listening for JSON-RPC over unix socket");
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let agent = agent.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let response = handle_request(&line, &agent).await;
+                    if writer.write_all(format!("{response}\n").as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    async fn listen_http(port: u16, agent: Arc<Mutex<Agent>>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use axum::{routing::post, Json, Router};
+
+        let app = Router::new().route(
+            "/rpc",
+            post(move |Json(body): Json<serde_json::Value>| {
+                let agent = agent.clone();
+                async move { Json(handle_value(body, &agent).await) }
+            }),
+        );
+
+        let addr: std::net::SocketAddr = format!("0.0.0.0:{}", port).parse()?;
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        tracing::info!(%addr, "listening for JSON-RPC over HTTP");
+        axum::serve(listener, app).await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    method: String,
+    params: JsonRpcChatParams,
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcChatParams {
+    message: String,
+    #[serde(default)]
+    session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i32,
+    message: String,
+}
+
+async fn handle_request(line: &str, agent: &Arc<Mutex<Agent>>) -> serde_json::Value {
+    match serde_json::from_str(line) {
+        Ok(value) => handle_value(value, agent).await,
+        Err(e) => serde_json::to_value(JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(JsonRpcError { code: -32700, message: format!("parse error: {e}") }),
+            id: serde_json::Value::Null,
+        })
+        .unwrap(),
+    }
+}
+
+async fn handle_value(value: serde_json::Value, agent: &Arc<Mutex<Agent>>) -> serde_json::Value {
+    let request: JsonRpcRequest = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => {
+            return serde_json::to_value(JsonRpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(JsonRpcError { code: -32600, message: format!("invalid request: {e}") }),
+                id: serde_json::Value::Null,
+            })
+            .unwrap()
+        }
+    };
+
+    if request.method != "chat" {
+        return serde_json::to_value(JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(JsonRpcError { code: -32601, message: format!("method not found: {}", request.method) }),
+            id: request.id,
+        })
+        .unwrap();
+    }
+
+    let response = agent.lock().await.run_once(&request.params.message).await;
+    let session_id = request
+        .params
+        .session_id
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    serde_json::to_value(JsonRpcResponse {
+        jsonrpc: "2.0",
+        result: Some(serde_json::json!({ "response": response, "session_id": session_id })),
+        error: None,
+        id: request.id,
+    })
+    .unwrap()
+}
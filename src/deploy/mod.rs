@@ -1,17 +1,34 @@
+pub mod auth;
+pub mod docker_scheduler;
+pub mod gateway;
 pub mod server;
+pub mod tls;
 
 use std::process::Command;
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
-/// Deploy an agent or team to Docker and start HTTP server
+use crate::runner::RunnerClient;
+use crate::team::dispatcher::build_team_agent;
+use crate::team::Team;
+use crate::workflow::runner::WorkflowRunner;
+
+pub use docker_scheduler::{DeploymentHandle, DockerEndpointConfig, DockerScheduler};
+
+/// Deploy an agent or team to Docker and start it on whichever configured
+/// Docker endpoint currently has the most free capacity (see
+/// `DockerEndpointConfig::from_env_or_default`). The image itself is still
+/// built through the local Docker CLI - shipping a build context over the
+/// HTTP API is a separate concern from scheduling where the container runs.
 pub async fn deploy_agent(
     _config_path: &str,
     port: u16,
     container_name: Option<String>,
     tag: String,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<DeploymentHandle, Box<dyn std::error::Error + Send + Sync>> {
     println!("🔧 Building Docker image...");
-    
+
     // Generate container name if not provided
     let name = container_name.unwrap_or_else(|| {
         let uuid = Uuid::new_v4();
@@ -20,19 +37,16 @@ pub async fn deploy_agent(
 
     // Build Docker image
     build_docker_image(&name, &tag)?;
-    
-    // Run container
-    let container_id = run_docker_container(&name, &tag, port)?;
-    
-    // Start HTTP server inside container
-    let url = format!("http://localhost:{}", port);
-    
+
+    // Pick a Docker endpoint with free capacity and run the container there
+    let scheduler = DockerScheduler::connect(DockerEndpointConfig::from_env_or_default()).await?;
+    let handle = scheduler.run_container(&name, &format!("{}:{}", name, tag), port).await?;
+
     println!("✅ Agent deployed successfully!");
-    println!("🌐 URL: {}", url);
-    println!("🆔 Container: {}", container_id);
-    println!("📊 Health check: {}/health", url);
-    
-    Ok(url)
+    println!("🌐 Endpoint: {}", handle.endpoint_uri);
+    println!("🆔 Container: {}", handle.container_id);
+
+    Ok(handle)
 }
 
 /// Build Docker image
@@ -50,65 +64,64 @@ fn build_docker_image(name: &str, tag: &str) -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
-/// Run Docker container
-fn run_docker_container(name: &str, tag: &str, port: u16) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let container_name = format!("{}-{}", name, Uuid::new_v4().to_string().split('-').next().unwrap());
-    
-    let output = Command::new("docker")
-        .args(&[
-            "run",
-            "-d",
-            "--name", &container_name,
-            "-p", &format!("{}:8080", port),
-            "-e", "RUST_LOG=info",
-            &format!("{}:{}", name, tag),
-        ])
-        .output()?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Docker run failed: {}", error).into());
-    }
-
-    let container_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    println!("✅ Container started: {}", container_id);
-    
-    Ok(container_id)
-}
-
-/// Stop and remove a deployed agent
-pub fn stop_agent(container_name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// Stop and remove a deployed agent on the Docker endpoint it was placed on.
+/// `endpoint` is the same `endpoint_uri` recorded on the `DeploymentHandle`
+/// `deploy_agent` returned, since a fresh process has no memory of it otherwise.
+pub async fn stop_agent(container_name: &str, endpoint: DockerEndpointConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     println!("🛑 Stopping agent: {}", container_name);
-    
-    // Stop container
-    let _ = Command::new("docker")
-        .args(&["stop", container_name])
-        .output()?;
 
-    // Remove container
-    let _ = Command::new("docker")
-        .args(&["rm", container_name])
-        .output()?;
+    let uri = endpoint.uri.clone();
+    let scheduler = DockerScheduler::connect(vec![endpoint]).await?;
+    scheduler.stop_container_on(&uri, container_name).await?;
 
     println!("✅ Agent stopped and removed");
     Ok(())
 }
 
-/// List all deployed agents
-pub fn list_deployed_agents() -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
-    let output = Command::new("docker")
-        .args(&["ps", "--filter", "ancestor=helixor", "--format", "{{.Names}}"])
-        .output()?;
+/// List all deployed agents across every configured Docker endpoint
+pub async fn list_deployed_agents(endpoints: Vec<DockerEndpointConfig>) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let scheduler = DockerScheduler::connect(endpoints).await?;
+    Ok(scheduler.list_containers().await)
+}
 
-    if !output.status.success() {
-        return Ok(Vec::new());
+/// Handle to a team deployed via `deploy_team_instance`; dropping it leaves
+/// the workers running, call `shutdown` to stop them
+pub struct TeamInstanceHandle {
+    workers: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl TeamInstanceHandle {
+    /// Abort every spawned worker loop
+    pub fn shutdown(self) {
+        for worker in self.workers {
+            worker.abort();
+        }
     }
+}
 
-    let containers = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|s| s.to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+/// Deploy every agent in `team` as a pull-mode worker instead of a reachable
+/// Docker container: each spawns a `RunnerClient` tagged with its
+/// `TeamAgent.role` that long-polls `coordinator_url` for work, so the team
+/// keeps functioning even when its members sit behind NAT/firewalls that
+/// can't accept inbound connections.
+pub async fn deploy_team_instance(
+    team: Team,
+    coordinator_url: impl Into<String>,
+) -> Result<TeamInstanceHandle, Box<dyn std::error::Error + Send + Sync>> {
+    let coordinator_url = coordinator_url.into();
+    let mut workers = Vec::new();
+
+    for team_agent in &team.agents {
+        let agent = build_team_agent(&team, team_agent).map_err(|e| e.to_string())?;
+        let agent = Arc::new(Mutex::new(agent));
+        let workflows = Arc::new(Mutex::new(WorkflowRunner::new()));
+        let role = Some(team_agent.role.clone());
+        let client = RunnerClient::new(coordinator_url.clone(), agent, workflows, role);
+
+        workers.push(tokio::spawn(async move {
+            client.run_forever().await;
+        }));
+    }
 
-    Ok(containers)
-} 
\ No newline at end of file
+    Ok(TeamInstanceHandle { workers })
+}
@@ -1,24 +1,138 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
-    routing::{get, post},
+    body::{Body, Bytes},
+    extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade},
+    extract::{Path, Request, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
     Router,
 };
+use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio_stream::wrappers::ReceiverStream;
 use crate::agent::Agent;
 use crate::agent::AgentBuilder;
+use crate::config::AgentConfig;
+use crate::deploy::auth::AuthConfig;
+use crate::deploy::gateway::{ConsoleGateway, Gateway, HttpGateway, JsonRpcGateway, JsonRpcTransport, UnixSocketGateway};
+use crate::deploy::tls::TlsConfig;
+use crate::error::HelixorError;
 use crate::model::openai::OpenAiClient;
 use crate::memory::sqlite::SqliteMemory;
+use crate::role::RoleRegistry;
 use crate::tool::ToolRegistry;
 use crate::grpc;
 
-/// Server state containing the agent
+/// `agent_id` a request implicitly targets when it doesn't name one, so existing
+/// single-agent configs and clients keep working unchanged
+pub const DEFAULT_AGENT_ID: &str = "default";
+
+/// Concurrent map of `agent_id -> Agent`, letting one running process host and
+/// administer several agents instead of being fixed to the one it started with
+#[derive(Clone, Default)]
+pub struct AgentRegistry {
+    agents: Arc<RwLock<HashMap<String, Arc<Mutex<Agent>>>>>,
+}
+
+impl AgentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a registry seeded with a single agent under `DEFAULT_AGENT_ID`,
+    /// matching how the server behaved before the registry existed
+    pub fn with_default(agent: Arc<Mutex<Agent>>) -> Self {
+        let registry = Self::new();
+        registry
+            .agents
+            .try_write()
+            .expect("registry is freshly created and uncontended")
+            .insert(DEFAULT_AGENT_ID.to_string(), agent);
+        registry
+    }
+
+    pub async fn insert(&self, agent_id: String, agent: Arc<Mutex<Agent>>) {
+        self.agents.write().await.insert(agent_id, agent);
+    }
+
+    pub async fn get(&self, agent_id: &str) -> Option<Arc<Mutex<Agent>>> {
+        self.agents.read().await.get(agent_id).cloned()
+    }
+
+    pub async fn remove(&self, agent_id: &str) -> Option<Arc<Mutex<Agent>>> {
+        self.agents.write().await.remove(agent_id)
+    }
+
+    pub async fn list(&self) -> Vec<AgentSummary> {
+        let mut summaries = Vec::new();
+        for (agent_id, agent) in self.agents.read().await.iter() {
+            let agent = agent.lock().await;
+            summaries.push(AgentSummary {
+                agent_id: agent_id.clone(),
+                name: agent.name.clone(),
+                tools_available: agent.tools.len(),
+            });
+        }
+        summaries
+    }
+}
+
+/// Server state: the registry of agents this process is hosting, the
+/// bearer-token secret (if any) guarding every API route (all but `/health`
+/// and the static playground/arena pages), the
+/// artifacts produced by `/tasks/stream` runs (keyed by the `object_id` handed
+/// out in that run's `artifact_create` frame), and the bookkeeping
+/// `run_shutdown`/`track_active_requests` use to drain in-flight
+/// chat/stream requests before the process exits
 #[derive(Clone)]
 pub struct AppState {
-    pub agent: Arc<Mutex<Agent>>,
+    pub registry: AgentRegistry,
+    pub auth: Option<AuthConfig>,
+    pub artifacts: Arc<RwLock<HashMap<String, ArtifactRecord>>>,
+    /// Number of `/chat`, `/chat/stream`, `/v1/chat/completions`, or
+    /// `/tasks/stream` requests whose response body hasn't finished yet
+    pub active_requests: Arc<AtomicUsize>,
+    /// Set once a shutdown signal has been received; surfaced via `/health`
+    /// so a load balancer stops routing new traffic here
+    pub draining: Arc<AtomicBool>,
+}
+
+/// An artifact created by a `/tasks/stream` run. Inserted (with `received:
+/// false`) the moment the server emits the `artifact_create` frame that names
+/// it, and filled in once the client's matching `POST /artifacts/{object_id}`
+/// arrives, so `/status` can distinguish an artifact the client never
+/// uploaded from one it did.
+#[derive(Clone, Serialize)]
+pub struct ArtifactRecord {
+    pub name: String,
+    pub description: String,
+    pub size: usize,
+    pub received: bool,
+    /// When this record was inserted; `sweep_expired_artifacts` uses this to
+    /// evict entries older than `ARTIFACT_TTL` so a long-lived server's
+    /// artifact map doesn't grow without bound across its lifetime.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How long an artifact record (announced or uploaded) is kept before
+/// `sweep_expired_artifacts` evicts it
+const ARTIFACT_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+/// Periodically drop artifact records older than `ARTIFACT_TTL`, so a
+/// deployed agent that serves `/tasks/stream` runs indefinitely doesn't leak
+/// memory proportional to total tasks ever run
+async fn sweep_expired_artifacts(artifacts: Arc<RwLock<HashMap<String, ArtifactRecord>>>) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(300)).await;
+        let now = chrono::Utc::now();
+        artifacts.write().await.retain(|_, record| now - record.created_at < ARTIFACT_TTL);
+    }
 }
 
 /// Request for agent interaction
@@ -26,6 +140,15 @@ pub struct AppState {
 pub struct AgentRequest {
     pub message: String,
     pub session_id: Option<String>,
+    /// Which registered agent should handle this request; defaults to
+    /// `DEFAULT_AGENT_ID` so single-agent deployments need not set it
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Request an SSE stream of deltas instead of a single buffered response;
+    /// an `Accept: text/event-stream` header has the same effect without
+    /// needing this field
+    #[serde(default)]
+    pub stream: bool,
 }
 
 /// Response from agent
@@ -44,6 +167,14 @@ pub struct HealthResponse {
     pub uptime: u64,
 }
 
+/// Summary of a registered agent, as returned by `GET /agents`
+#[derive(Serialize)]
+pub struct AgentSummary {
+    pub agent_id: String,
+    pub name: String,
+    pub tools_available: usize,
+}
+
 /// Start server from config file
 pub async fn start_server_from_config(
     config_path: &str,
@@ -51,134 +182,928 @@ pub async fn start_server_from_config(
     grpc_port: Option<u16>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Load agent from config
-    let agent = Arc::new(Mutex::new(load_agent_from_config(config_path).await?));
-    
+    let agent = Arc::new(Mutex::new(load_agent_from_config(config_path, None).await?));
+    let registry = AgentRegistry::with_default(agent.clone());
+
+    // A `[tls]` section in the same config file enables HTTPS/TLS-terminated gRPC
+    // declaratively; absent, servers fall back to plaintext as before.
+    let config_content = std::fs::read_to_string(config_path)?;
+    let config: crate::config::AgentConfig = toml::from_str(&config_content)?;
+    let tls = config.tls;
+
+    // Gateways named under `[gateways]` run alongside the primary HTTP/gRPC pair
+    // in the background, so the same agent can also be driven from a terminal, a
+    // raw socket, or JSON-RPC without changing how the primary servers start.
+    if config.gateways.console {
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            let _ = ConsoleGateway.listen(registry).await;
+        });
+    }
+    if let Some(path) = config.gateways.unix_socket.clone() {
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            if let Err(e) = (UnixSocketGateway { path }).listen(registry).await {
+                tracing::error!(error = %e, "unix socket gateway exited");
+            }
+        });
+    }
+    if let Some(path) = config.gateways.json_rpc_socket.clone() {
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            let gateway = JsonRpcGateway { transport: JsonRpcTransport::UnixSocket(path) };
+            if let Err(e) = gateway.listen(registry).await {
+                tracing::error!(error = %e, "JSON-RPC socket gateway exited");
+            }
+        });
+    }
+    if let Some(port) = config.gateways.json_rpc_http_port {
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            let gateway = JsonRpcGateway { transport: JsonRpcTransport::Http(port) };
+            if let Err(e) = gateway.listen(registry).await {
+                tracing::error!(error = %e, "JSON-RPC HTTP gateway exited");
+            }
+        });
+    }
+
     // Start both HTTP and gRPC servers
     if let Some(grpc_port) = grpc_port {
-        let http_agent = agent.clone();
         let grpc_agent = agent.clone();
-        
-        let http_future = start_server(http_agent, http_port);
-        let grpc_future = grpc::start_grpc_server(grpc_agent, grpc_port);
-        
+
+        let http_future = (HttpGateway { port: http_port, tls: tls.clone() }).listen(registry);
+        let grpc_future = grpc::start_grpc_server(grpc_agent, grpc_port, tls);
+
         tokio::try_join!(http_future, grpc_future)?;
     } else {
         // Start only HTTP server
-        start_server(agent, http_port).await?;
+        (HttpGateway { port: http_port, tls }).listen(registry).await?;
     }
-    
+
     Ok(())
 }
 
-/// Load agent from config file
-async fn load_agent_from_config(config_path: &str) -> Result<Agent, Box<dyn std::error::Error + Send + Sync>> {
-    // Load config
+/// Load agent from config file, optionally starting it as a named persona. When
+/// `role` names an entry in the config's `[roles]` table, that persona's system
+/// prompt (and, if set, its model/tool overrides) replace the config's defaults;
+/// an unknown role name is reported rather than silently ignored.
+pub(crate) async fn load_agent_from_config(
+    config_path: &str,
+    role: Option<&str>,
+) -> Result<Agent, Box<dyn std::error::Error + Send + Sync>> {
     let config_content = std::fs::read_to_string(config_path)?;
     let config: crate::config::AgentConfig = toml::from_str(&config_content)?;
-    
-    // Get API key from environment
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .expect("OPENAI_API_KEY environment variable must be set");
-    
+    build_agent_from_config(&config, role).await
+}
+
+/// Build an agent from an already-parsed config, optionally starting it as a
+/// named persona. Shared by `load_agent_from_config` (reading the config from
+/// disk) and `POST /agents` (taking the config straight from the request body).
+pub(crate) async fn build_agent_from_config(
+    config: &AgentConfig,
+    role: Option<&str>,
+) -> Result<Agent, Box<dyn std::error::Error + Send + Sync>> {
+    let role_registry = RoleRegistry::from_map(config.roles.clone());
+    let persona = match role {
+        Some(name) => Some(role_registry.get(name).ok_or_else(|| {
+            if role_registry.is_empty() {
+                format!("unknown role '{}': this config declares no [roles] table", name)
+            } else {
+                format!("unknown role '{}': not declared in this config's [roles] table", name)
+            }
+        })?),
+        None => None,
+    };
+
+    let model_id = persona
+        .and_then(|r| r.model.as_ref())
+        .unwrap_or(&config.model);
+
+    // Resolve the model identifier (a `provider:model` identifier) against the
+    // providers declared in the config file, falling back to a plain OpenAI
+    // client built from OPENAI_API_KEY when no `[providers]` section is present —
+    // this keeps existing agent.toml files without a `providers` table working
+    // unchanged.
+    let model: Box<dyn crate::model::Model + Send + Sync> = if config.providers.is_empty() {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .expect("OPENAI_API_KEY environment variable must be set");
+        Box::new(OpenAiClient::new(api_key))
+    } else {
+        let registry = crate::model::ModelRegistry::from_configs(config.providers.clone());
+        registry.build(model_id)?
+    };
+
+    let instructions = persona
+        .map(|r| r.system_prompt.as_str())
+        .unwrap_or(&config.instructions);
+    let tools = persona
+        .and_then(|r| r.tools.as_ref())
+        .unwrap_or(&config.tools);
+
     // Create tool registry
     let _registry = ToolRegistry::new();
-    
+
     // Build agent
     let agent = AgentBuilder::new(&config.name)
-        .with_instructions(&config.instructions)
-        .with_model(Box::new(OpenAiClient::new(api_key)))
+        .with_instructions(instructions)
+        .with_model(model)
         .with_memory(Arc::new(SqliteMemory::new("memory.db")?))
-        .with_tools(crate::tool::load_tools(&config.tools))
+        .with_tools(crate::tool::load_tools(tools))
+        .with_err_chan(crate::errchan::ErrChan::spawn())
         .build();
-    
+
     Ok(agent)
 }
 
-/// Create and start the HTTP server
-pub async fn start_server(agent: Arc<Mutex<Agent>>, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// Create and start the HTTP server, terminating TLS with `tls`'s cert/key when
+/// present and falling back to plaintext otherwise
+pub async fn start_server(
+    registry: AgentRegistry,
+    port: u16,
+    tls: Option<TlsConfig>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let auth = AuthConfig::from_env();
+    if auth.is_some() {
+        println!("🔑 Bearer-token auth enabled for all API endpoints (not /health or the playground pages)");
+    }
     let state = AppState {
-        agent,
+        registry,
+        auth,
+        artifacts: Arc::new(RwLock::new(HashMap::new())),
+        active_requests: Arc::new(AtomicUsize::new(0)),
+        draining: Arc::new(AtomicBool::new(false)),
     };
+    tokio::spawn(sweep_expired_artifacts(state.artifacts.clone()));
 
-    let app = Router::new()
-        .route("/health", get(health_check))
+    // The chat/stream endpoints are the ones worth draining on shutdown -
+    // they can run for tens of seconds mid-generation - so `track_active_requests`
+    // wraps only this subset rather than every protected route.
+    let streaming_routes = Router::new()
         .route("/chat", post(chat_with_agent))
+        .route("/chat/stream", get(chat_stream_ws))
+        .route("/v1/chat/completions", post(openai_chat_completions))
+        .route("/tasks/stream", post(task_stream))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), track_active_requests));
+
+    // Every API route (all but `/health`, since liveness probes shouldn't need a
+    // token, and the static pages below) goes through `require_auth` when an
+    // `AuthConfig` is configured
+    let protected = Router::new()
+        .merge(streaming_routes)
+        .route("/v1/models", get(openai_list_models))
+        .route("/artifacts/:object_id", post(upload_artifact))
         .route("/status", get(get_status))
-        .with_state(state);
+        .route("/agents", get(list_agents).post(create_agent))
+        .route("/agents/:id", delete(delete_agent))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), require_auth));
 
-    let addr = format!("0.0.0.0:{}", port);
-    println!("üåê Starting HTTP server on {}", addr);
+    // The embedded playground/arena pages are plain static HTML/JS compiled
+    // into the binary (see `PLAYGROUND_HTML`/`ARENA_HTML`); they live outside
+    // `protected` since they're just assets - the JSON API calls the page's
+    // own JS makes still go through `require_auth` like any other caller.
+    let static_routes = Router::new()
+        .route("/", get(serve_playground))
+        .route("/arena", get(serve_arena));
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(static_routes)
+        .merge(protected)
+        .with_state(state.clone());
+
+    let addr: std::net::SocketAddr = format!("0.0.0.0:{}", port).parse()?;
+
+    // Both branches go through axum-server's `Handle` rather than axum::serve's
+    // plain `with_graceful_shutdown`: a `Handle` lets us stop accepting new
+    // connections the instant the signal arrives *and* gives axum-server a
+    // hard cutoff to force-close anything still open once the grace period
+    // elapses - `axum::serve`'s own graceful-shutdown phase, which used to run
+    // after our bounded drain-wait here, has no such cutoff and could hang the
+    // process indefinitely on a stuck connection.
+    let handle = axum_server::Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(run_shutdown(state.draining, state.active_requests, shutdown_handle));
+
+    match tls {
+        Some(tls) => {
+            println!("\u{1F512} Starting HTTPS server on {}", addr);
+            let rustls_config = tls.load().await?;
+
+            axum_server::bind_rustls(addr, rustls_config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        None => {
+            println!("\u{1F310} Starting HTTP server on {}", addr);
+            axum_server::bind(addr).handle(handle).serve(app.into_make_service()).await?;
+        }
+    }
 
     Ok(())
 }
 
-/// Health check endpoint
+/// Rejects requests that don't carry `Authorization: Bearer <state.auth's secret>`
+/// with a `401` and a JSON error body; a no-op pass-through when `state.auth` is
+/// `None` (no secret configured), same as an unset `[tls]` table leaves the
+/// server in plaintext. Applied via `route_layer` to every API route, i.e. all
+/// but `/health` and the static playground/arena pages.
+async fn require_auth(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let Some(auth) = &state.auth else {
+        return next.run(request).await;
+    };
+
+    let header = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    if auth.accepts(header) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": "missing or invalid bearer token" })),
+        )
+            .into_response()
+    }
+}
+
+/// Default grace period for in-flight chat/stream requests to finish after a
+/// shutdown signal, overridable via `SHUTDOWN_GRACE_SECONDS`
+const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// Resolves on SIGINT, or (on Unix) SIGTERM - the two signals a container
+/// orchestrator or `^C` actually sends before killing the process
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to install SIGTERM handler"),
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Polls `active` down to zero; bounded by the `tokio::time::timeout` the
+/// caller wraps this in, since a stuck connection shouldn't hang shutdown forever
+async fn wait_until_drained(active: Arc<AtomicUsize>) {
+    while active.load(Ordering::SeqCst) > 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Waits for a shutdown signal, flips `draining` (so `/health`/`/status` can
+/// tell a load balancer to stop routing here), tells `handle` to stop
+/// accepting new connections *immediately* and force-close anything still
+/// open after `SHUTDOWN_GRACE_SECONDS` (default `DEFAULT_SHUTDOWN_GRACE`) -
+/// the hard cutoff axum-server enforces regardless of what this function does
+/// - then separately polls the chat/stream requests `track_active_requests`
+/// is counting so it can log whether they drained in time. There's no
+/// separate memory-flush step: `SqliteMemory::store` commits synchronously on
+/// every call, so once a request has finished, whatever it wrote is already
+/// durable.
+async fn run_shutdown(draining: Arc<AtomicBool>, active: Arc<AtomicUsize>, handle: axum_server::Handle) {
+    wait_for_shutdown_signal().await;
+    println!("🛑 Shutdown signal received, draining in-flight requests...");
+    draining.store(true, Ordering::SeqCst);
+
+    let grace_period = std::env::var("SHUTDOWN_GRACE_SECONDS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_SHUTDOWN_GRACE);
+
+    handle.graceful_shutdown(Some(grace_period));
+
+    if tokio::time::timeout(grace_period, wait_until_drained(active.clone())).await.is_err() {
+        println!(
+            "⚠️ Grace period elapsed with {} request(s) still in flight",
+            active.load(Ordering::SeqCst)
+        );
+    } else {
+        println!("✅ All in-flight requests drained");
+    }
+}
+
+/// RAII guard counted in `AppState.active_requests`, attached to a tracked
+/// response's body (see `track_active_requests`) so the count reflects the
+/// whole lifetime of an in-flight request - including time spent streaming a
+/// long reply - not just how long the handler function itself took to return.
+struct ActiveRequestGuard(Arc<AtomicUsize>);
+
+impl ActiveRequestGuard {
+    fn new(counter: Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for ActiveRequestGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Wraps `/chat`, `/chat/stream`, `/v1/chat/completions`, and `/tasks/stream`
+/// so `run_shutdown` knows how many of them are still in flight: an
+/// `ActiveRequestGuard` is created before the handler runs and chained onto
+/// the end of the response body, so it's only dropped (decrementing the
+/// count) once that body has been fully read or the connection drops,
+/// whichever happens first.
+async fn track_active_requests(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let guard = ActiveRequestGuard::new(state.active_requests.clone());
+    let response = next.run(request).await;
+
+    let (parts, body) = response.into_parts();
+    let tail = stream::once(async move {
+        drop(guard);
+        Ok::<Bytes, axum::Error>(Bytes::new())
+    });
+    let body = Body::from_stream(body.into_data_stream().chain(tail));
+
+    Response::from_parts(parts, body)
+}
+
+/// Health check endpoint, reporting on the default agent. Reports
+/// `status: "draining"` once a shutdown signal has been received, so a load
+/// balancer stops sending new traffic here while in-flight work finishes.
+#[tracing::instrument(skip(state))]
 async fn health_check(
     State(state): State<AppState>,
-) -> Json<HealthResponse> {
-    let agent = state.agent.lock().await;
-    
-    Json(HealthResponse {
-        status: "healthy".to_string(),
+) -> Result<Json<HealthResponse>, HelixorError> {
+    let agent = state
+        .registry
+        .get(DEFAULT_AGENT_ID)
+        .await
+        .ok_or_else(|| HelixorError::AgentUnavailable(DEFAULT_AGENT_ID.to_string()))?;
+    let agent = agent.lock().await;
+
+    let status = if state.draining.load(Ordering::SeqCst) { "draining" } else { "healthy" };
+
+    Ok(Json(HealthResponse {
+        status: status.to_string(),
         agent_name: agent.name.clone(),
         uptime: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs(),
-    })
+    }))
+}
+
+/// The embedded single-agent playground, compiled into the binary so
+/// `cargo run --example deploy_agent` is fully self-contained with no assets
+/// to ship separately. Its JS talks to `/chat` directly.
+const PLAYGROUND_HTML: &[u8] = include_bytes!("../../web/playground.html");
+
+/// The embedded arena page: the same prompt sent to two selectable agents
+/// concurrently, rendered side by side. Its JS talks to `/v1/models` and `/chat`.
+const ARENA_HTML: &[u8] = include_bytes!("../../web/arena.html");
+
+/// Serve the embedded playground at `/`
+async fn serve_playground() -> impl IntoResponse {
+    ([(axum::http::header::CONTENT_TYPE, "text/html; charset=utf-8")], PLAYGROUND_HTML)
 }
 
-/// Chat with the agent
+/// Serve the embedded arena page at `/arena`
+async fn serve_arena() -> impl IntoResponse {
+    ([(axum::http::header::CONTENT_TYPE, "text/html; charset=utf-8")], ARENA_HTML)
+}
+
+/// Chat with an agent, routed by `request.agent_id` (defaulting to `DEFAULT_AGENT_ID`).
+/// Buffers the full reply into a single `AgentResponse` by default; if the caller
+/// sends `Accept: text/event-stream` or `"stream": true`, responds instead with an
+/// SSE stream of `{"delta": ..., "session_id": ...}` frames followed by a
+/// `data: [DONE]` sentinel, so a frontend can show tokens as they're generated
+/// instead of waiting on `agent.run_once`.
+#[tracing::instrument(skip(state, headers, request), fields(session_id, agent_id))]
 async fn chat_with_agent(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<AgentRequest>,
-) -> Result<Json<AgentResponse>, StatusCode> {
-    let mut agent = state.agent.lock().await;
-    
+) -> Result<Response, HelixorError> {
+    let session_id = request.session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let agent_id = request.agent_id.as_deref().unwrap_or(DEFAULT_AGENT_ID);
+    tracing::Span::current().record("session_id", &session_id.as_str());
+    tracing::Span::current().record("agent_id", agent_id);
+
+    let wants_stream = request.stream
+        || headers
+            .get(axum::http::header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("text/event-stream"));
+
+    let agent = state
+        .registry
+        .get(agent_id)
+        .await
+        .ok_or_else(|| HelixorError::AgentUnavailable(agent_id.to_string()))?;
+
+    if wants_stream {
+        let mut guard = agent.lock().await;
+        let fragments = guard.run_stream(&request.message).await;
+        drop(guard);
+
+        let deltas = fragments.map(move |delta| {
+            let frame = serde_json::json!({ "delta": delta, "session_id": session_id });
+            Ok::<Event, std::convert::Infallible>(Event::default().data(frame.to_string()))
+        });
+        let done = stream::once(async { Ok(Event::default().data("[DONE]")) });
+
+        return Ok(Sse::new(deltas.chain(done)).keep_alive(KeepAlive::default()).into_response());
+    }
+
+    let mut agent = agent.lock().await;
     let response = agent.run_once(&request.message).await;
-    let session_id = request.session_id.unwrap_or_else(|| {
-        uuid::Uuid::new_v4().to_string()
-    });
 
     Ok(Json(AgentResponse {
         response,
         session_id,
         timestamp: chrono::Utc::now(),
-    }))
+    })
+    .into_response())
+}
+
+/// Upgrade to a WebSocket and stream the default agent's reply one token at a
+/// time, instead of blocking until the full response is ready like `/chat`
+async fn chat_stream_ws(State(state): State<AppState>, ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_chat_stream(socket, state))
+}
+
+/// Per-connection loop: read one text frame as the user's message, forward the
+/// agent's streamed fragments as they arrive, then send a final frame carrying
+/// the session id and timestamp so the client knows the answer is complete
+#[tracing::instrument(skip(socket, state), fields(session_id = tracing::field::Empty))]
+async fn handle_chat_stream(mut socket: WebSocket, state: AppState) {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    tracing::Span::current().record("session_id", session_id.as_str());
+
+    let Some(Ok(WsMessage::Text(input))) = socket.recv().await else {
+        return;
+    };
+
+    let Some(agent) = state.registry.get(DEFAULT_AGENT_ID).await else {
+        return;
+    };
+    let mut agent = agent.lock().await;
+    let mut fragments = agent.run_stream(&input).await;
+
+    while let Some(fragment) = fragments.next().await {
+        if socket.send(WsMessage::Text(fragment)).await.is_err() {
+            tracing::warn!("client disconnected mid-stream");
+            return;
+        }
+    }
+
+    let final_frame = serde_json::json!({
+        "session_id": session_id,
+        "timestamp": chrono::Utc::now(),
+    });
+    let _ = socket.send(WsMessage::Text(final_frame.to_string())).await;
+}
+
+/// A frame exchanged over `/tasks/stream`'s newline-delimited JSON channel.
+/// `TaskStart` is the one frame the client ever sends, kicking off the run;
+/// everything else flows server -> client.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TaskControlMessage {
+    /// Kick off a run of `agent_id` (defaulting to `DEFAULT_AGENT_ID`) on `input`
+    TaskStart {
+        #[serde(default)]
+        agent_id: Option<String>,
+        input: String,
+    },
+    /// One fragment of the agent's reply, in generation order
+    Step { output: String },
+    /// An artifact the run produced; the client is expected to follow up with
+    /// `POST /artifacts/{object_id}` carrying its bytes
+    ArtifactCreate {
+        object_id: String,
+        name: String,
+        description: String,
+    },
+    /// The run is finished; no further frames follow
+    TaskDone,
+}
+
+/// Serialize `message` as one NDJSON line, newline-terminated so the reader
+/// on the other end can frame it without a length prefix
+fn task_frame(message: &TaskControlMessage) -> String {
+    format!("{}\n", serde_json::to_string(message).unwrap_or_default())
+}
+
+/// Long-lived duplex job channel for agent runs that take minutes and may
+/// produce file artifacts (a generated report, code, a plot), modeled on a CI
+/// runner: the request body and the response body are each a stream of
+/// newline-delimited `TaskControlMessage` JSON frames instead of a single
+/// buffered request/response pair. The client sends one `task_start` frame;
+/// the server streams back `step` frames as the agent's reply generates, an
+/// `artifact_create` frame once the run's output is ready to be archived, and
+/// a final `task_done` frame. See `upload_artifact` for the matching half of
+/// the artifact handoff.
+#[tracing::instrument(skip(state, request))]
+async fn task_stream(State(state): State<AppState>, request: Request) -> Response {
+    let mut body = request.into_body().into_data_stream();
+
+    // The client sends exactly one frame (`task_start`), so read just until
+    // the first newline instead of keeping the request body open.
+    let mut buf: Vec<u8> = Vec::new();
+    let first_line = loop {
+        match body.next().await {
+            Some(Ok(chunk)) => {
+                buf.extend_from_slice(&chunk);
+                if let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buf.drain(..=pos).collect();
+                    break String::from_utf8_lossy(&line).trim().to_string();
+                }
+            }
+            Some(Err(e)) => {
+                tracing::warn!(error = %e, "task stream body read failed");
+                return (StatusCode::BAD_REQUEST, "failed to read request body").into_response();
+            }
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    "connection closed before a task_start frame arrived",
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    let (agent_id, input) = match serde_json::from_str::<TaskControlMessage>(&first_line) {
+        Ok(TaskControlMessage::TaskStart { agent_id, input }) => (agent_id, input),
+        Ok(_) => return (StatusCode::BAD_REQUEST, "first frame must be task_start").into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, format!("malformed task_start frame: {e}")).into_response(),
+    };
+    let agent_id = agent_id.unwrap_or_else(|| DEFAULT_AGENT_ID.to_string());
+
+    let Some(agent) = state.registry.get(&agent_id).await else {
+        return HelixorError::AgentUnavailable(agent_id).into_response();
+    };
+
+    // Bounded so a slow client applies backpressure to generation, same as
+    // `/chat`'s SSE mode and `/chat/stream`'s WebSocket loop.
+    let (tx, rx) = mpsc::channel::<String>(16);
+    let artifacts = state.artifacts.clone();
+
+    tokio::spawn(async move {
+        let mut guard = agent.lock().await;
+        let mut fragments = guard.run_stream(&input).await;
+        drop(guard);
+
+        let mut response = String::new();
+        while let Some(delta) = fragments.next().await {
+            response.push_str(&delta);
+            if tx.send(task_frame(&TaskControlMessage::Step { output: delta })).await.is_err() {
+                return;
+            }
+        }
+
+        let object_id = uuid::Uuid::new_v4().to_string();
+        let name = "response.txt".to_string();
+        let description = "Agent's final reply for this task".to_string();
+        artifacts.write().await.insert(
+            object_id.clone(),
+            ArtifactRecord {
+                name: name.clone(),
+                description: description.clone(),
+                size: 0,
+                received: false,
+                created_at: chrono::Utc::now(),
+            },
+        );
+
+        let announce = TaskControlMessage::ArtifactCreate { object_id, name, description };
+        if tx.send(task_frame(&announce)).await.is_err() {
+            return;
+        }
+
+        let _ = tx.send(task_frame(&TaskControlMessage::TaskDone)).await;
+    });
+
+    Response::new(Body::from_stream(ReceiverStream::new(rx).map(Ok::<_, std::io::Error>)))
+}
+
+/// Store the bytes of an artifact a `/tasks/stream` run announced via its
+/// `artifact_create` frame. 404s if `object_id` was never announced, so a
+/// client can't park arbitrary blobs under ids it made up itself.
+async fn upload_artifact(
+    State(state): State<AppState>,
+    Path(object_id): Path<String>,
+    body: Bytes,
+) -> StatusCode {
+    let mut artifacts = state.artifacts.write().await;
+    match artifacts.get_mut(&object_id) {
+        Some(record) => {
+            record.size = body.len();
+            record.received = true;
+            StatusCode::NO_CONTENT
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// One message in an OpenAI-wire `messages` array; used for both the request
+/// (`role`/`content` only) and embedded in the response's `choices[].message`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct OpenAiMessage {
+    role: String,
+    content: String,
+}
+
+/// `POST /v1/chat/completions` request body, OpenAI wire format
+#[derive(Deserialize)]
+struct OpenAiChatCompletionRequest {
+    /// Looked up against the registry as an `agent_id`; falls back to
+    /// `DEFAULT_AGENT_ID` if no agent is registered under this name, so
+    /// existing OpenAI clients pointed at a single-agent deployment need not
+    /// know its real name
+    model: String,
+    messages: Vec<OpenAiMessage>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct OpenAiChoice {
+    index: usize,
+    message: OpenAiMessage,
+    finish_reason: String,
+}
+
+#[derive(Serialize)]
+struct OpenAiUsage {
+    prompt_tokens: usize,
+    completion_tokens: usize,
+    total_tokens: usize,
+}
+
+/// `chat.completion` response body, OpenAI wire format
+#[derive(Serialize)]
+struct OpenAiChatCompletionResponse {
+    id: String,
+    object: &'static str,
+    created: i64,
+    model: String,
+    choices: Vec<OpenAiChoice>,
+    usage: OpenAiUsage,
+}
+
+#[derive(Serialize)]
+struct OpenAiChunkDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+#[derive(Serialize)]
+struct OpenAiChunkChoice {
+    index: usize,
+    delta: OpenAiChunkDelta,
+    finish_reason: Option<&'static str>,
+}
+
+/// `chat.completion.chunk` frame, OpenAI wire format, sent as SSE data when
+/// `stream: true`
+#[derive(Serialize)]
+struct OpenAiChatCompletionChunk {
+    id: String,
+    object: &'static str,
+    created: i64,
+    model: String,
+    choices: Vec<OpenAiChunkChoice>,
+}
+
+/// OpenAI-compatible `/v1/chat/completions`: maps `model` onto an `agent_id`
+/// in the registry and the last message's content onto `Agent::run_once`'s
+/// input (the full conversation history otherwise already lives in the
+/// agent's own memory, same as `/chat`), so clients built against the OpenAI
+/// SDK or a tool expecting that wire format work against a deployed agent with
+/// no custom integration code. Reuses the same SSE machinery as `/chat`'s
+/// streaming mode for `stream: true`, just framed as `chat.completion.chunk`.
+#[tracing::instrument(skip(state, request), fields(model = %request.model))]
+async fn openai_chat_completions(
+    State(state): State<AppState>,
+    Json(request): Json<OpenAiChatCompletionRequest>,
+) -> Result<Response, HelixorError> {
+    let agent_id = if state.registry.get(&request.model).await.is_some() {
+        request.model.clone()
+    } else {
+        DEFAULT_AGENT_ID.to_string()
+    };
+    let agent = state
+        .registry
+        .get(&agent_id)
+        .await
+        .ok_or_else(|| HelixorError::AgentUnavailable(agent_id.clone()))?;
+
+    let input = request
+        .messages
+        .last()
+        .map(|m| m.content.clone())
+        .unwrap_or_default();
+    let prompt_tokens = input.split_whitespace().count();
+
+    let id = format!("chatcmpl-{}", uuid::Uuid::new_v4());
+    let created = chrono::Utc::now().timestamp();
+    let model = request.model.clone();
+
+    if request.stream {
+        let mut guard = agent.lock().await;
+        let fragments = guard.run_stream(&input).await;
+        drop(guard);
+
+        let id_for_chunks = id.clone();
+        let model_for_chunks = model.clone();
+        let deltas = fragments.map(move |content| {
+            let chunk = OpenAiChatCompletionChunk {
+                id: id_for_chunks.clone(),
+                object: "chat.completion.chunk",
+                created,
+                model: model_for_chunks.clone(),
+                choices: vec![OpenAiChunkChoice {
+                    index: 0,
+                    delta: OpenAiChunkDelta { content: Some(content) },
+                    finish_reason: None,
+                }],
+            };
+            Ok::<Event, std::convert::Infallible>(Event::default().data(
+                serde_json::to_string(&chunk).unwrap_or_default(),
+            ))
+        });
+        let final_chunk = OpenAiChatCompletionChunk {
+            id: id.clone(),
+            object: "chat.completion.chunk",
+            created,
+            model: model.clone(),
+            choices: vec![OpenAiChunkChoice { index: 0, delta: OpenAiChunkDelta { content: None }, finish_reason: Some("stop") }],
+        };
+        let final_frame = stream::once(async move {
+            Ok(Event::default().data(serde_json::to_string(&final_chunk).unwrap_or_default()))
+        });
+        let done = stream::once(async { Ok(Event::default().data("[DONE]")) });
+
+        return Ok(Sse::new(deltas.chain(final_frame).chain(done))
+            .keep_alive(KeepAlive::default())
+            .into_response());
+    }
+
+    let mut agent = agent.lock().await;
+    let response = agent.run_once(&input).await;
+    let completion_tokens = response.split_whitespace().count();
+
+    Ok(Json(OpenAiChatCompletionResponse {
+        id,
+        object: "chat.completion",
+        created,
+        model,
+        choices: vec![OpenAiChoice {
+            index: 0,
+            message: OpenAiMessage { role: "assistant".to_string(), content: response },
+            finish_reason: "stop".to_string(),
+        }],
+        usage: OpenAiUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        },
+    })
+    .into_response())
+}
+
+#[derive(Serialize)]
+struct OpenAiModel {
+    id: String,
+    object: &'static str,
+    owned_by: &'static str,
 }
 
-/// Get agent status
+#[derive(Serialize)]
+struct OpenAiModelList {
+    object: &'static str,
+    data: Vec<OpenAiModel>,
+}
+
+/// OpenAI-compatible `GET /v1/models`: lists every agent this process is
+/// hosting as a "model", keyed by its `agent_id`, so a client can discover
+/// what it can pass as `model` to `/v1/chat/completions`
+async fn openai_list_models(State(state): State<AppState>) -> Json<OpenAiModelList> {
+    let data = state
+        .registry
+        .list()
+        .await
+        .into_iter()
+        .map(|summary| OpenAiModel {
+            id: summary.agent_id,
+            object: "model",
+            owned_by: "helixor",
+        })
+        .collect();
+
+    Json(OpenAiModelList { object: "list", data })
+}
+
+/// List every agent currently hosted by this process
+async fn list_agents(State(state): State<AppState>) -> Json<Vec<AgentSummary>> {
+    Json(state.registry.list().await)
+}
+
+/// Build and register a new agent from a posted `AgentConfig`, using its `name`
+/// as the `agent_id`; an agent already registered under that name is replaced
+#[tracing::instrument(skip(state, config), fields(agent_id = %config.name))]
+async fn create_agent(
+    State(state): State<AppState>,
+    Json(config): Json<AgentConfig>,
+) -> Result<(StatusCode, Json<AgentSummary>), StatusCode> {
+    let agent_id = config.name.clone();
+    let agent = build_agent_from_config(&config, None)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "failed to build agent from posted config");
+            StatusCode::BAD_REQUEST
+        })?;
+
+    let summary = AgentSummary {
+        agent_id: agent_id.clone(),
+        name: agent.name.clone(),
+        tools_available: agent.tools.len(),
+    };
+    state.registry.insert(agent_id, Arc::new(Mutex::new(agent))).await;
+
+    Ok((StatusCode::CREATED, Json(summary)))
+}
+
+/// Remove a registered agent; the default agent can be removed too, after
+/// which requests naming it fail with `AgentUnavailable` (503) until a new one
+/// is created under that id
+async fn delete_agent(State(state): State<AppState>, Path(agent_id): Path<String>) -> StatusCode {
+    match state.registry.remove(&agent_id).await {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// Get default agent status
 async fn get_status(
     State(state): State<AppState>,
-) -> Json<serde_json::Value> {
-    let agent = state.agent.lock().await;
-    
-    Json(serde_json::json!({
+) -> Result<Json<serde_json::Value>, HelixorError> {
+    let agent = state
+        .registry
+        .get(DEFAULT_AGENT_ID)
+        .await
+        .ok_or_else(|| HelixorError::AgentUnavailable(DEFAULT_AGENT_ID.to_string()))?;
+    let agent = agent.lock().await;
+    let artifacts: Vec<ArtifactRecord> = state.artifacts.read().await.values().cloned().collect();
+    let status = if state.draining.load(Ordering::SeqCst) { "draining" } else { "running" };
+
+    Ok(Json(serde_json::json!({
         "name": agent.name,
-        "status": "running",
+        "status": status,
         "memory_backend": "sqlite",
-        "tools_available": agent.tools.len()
-    }))
-} 
+        "tools_available": agent.tools.len(),
+        "artifacts": artifacts,
+        "active_requests": state.active_requests.load(Ordering::SeqCst),
+    })))
+}
 
 /// Start server for a programmatically constructed Agent
 pub async fn start_agent_server(
     agent: Agent,
     http_port: u16,
     grpc_port: Option<u16>,
+    tls: Option<TlsConfig>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let agent = Arc::new(Mutex::new(agent));
+    let registry = AgentRegistry::with_default(agent.clone());
     if let Some(grpc_port) = grpc_port {
-        let http_agent = agent.clone();
         let grpc_agent = agent.clone();
-        let http_future = start_server(http_agent, http_port);
-        let grpc_future = crate::grpc::start_grpc_server(grpc_agent, grpc_port);
+        let http_future = start_server(registry, http_port, tls.clone());
+        let grpc_future = crate::grpc::start_grpc_server(grpc_agent, grpc_port, tls);
         tokio::try_join!(http_future, grpc_future)?;
     } else {
-        start_server(agent, http_port).await?;
+        start_server(registry, http_port, tls).await?;
     }
     Ok(())
 }
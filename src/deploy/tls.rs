@@ -0,0 +1,27 @@
+use axum_server::tls_rustls::RustlsConfig;
+use serde::Deserialize;
+
+/// Paths to a PEM certificate/key pair used to terminate TLS on a deployed
+/// agent's HTTP and gRPC listeners. Declared under an agent config's `[tls]`
+/// table; when absent, servers fall back to plaintext as before.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    pub cert_path: String,
+    pub key_path: String,
+}
+
+impl TlsConfig {
+    /// Load the PEM files into a rustls server config axum-server can bind with
+    pub async fn load(&self) -> std::io::Result<RustlsConfig> {
+        RustlsConfig::from_pem_file(&self.cert_path, &self.key_path).await
+    }
+
+    /// Load the same PEM files into a tonic server TLS config, so the gRPC
+    /// listener can terminate TLS with the identical certificate/key pair
+    /// the HTTP gateway uses
+    pub async fn load_tonic(&self) -> std::io::Result<tonic::transport::ServerTlsConfig> {
+        let cert = tokio::fs::read(&self.cert_path).await?;
+        let key = tokio::fs::read(&self.key_path).await?;
+        Ok(tonic::transport::ServerTlsConfig::new().identity(tonic::transport::Identity::from_pem(cert, key)))
+    }
+}
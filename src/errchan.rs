@@ -0,0 +1,70 @@
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// One failure reported to the central error channel: what went wrong, and
+/// which subsystem it came from, e.g. `"tool:search"` or `"model:openai"`
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    pub source: String,
+    pub message: String,
+}
+
+/// How many times in all the background task tries to report a batch before
+/// giving up and dropping it
+const MAX_REPORT_ATTEMPTS: usize = 3;
+
+/// Central async error-reporting channel. Tool failures and model errors are
+/// sent here instead of being printed inline at the call site; a background
+/// task batches whatever arrived together and reports it, retrying a few times
+/// before dropping the batch.
+#[derive(Clone)]
+pub struct ErrChan {
+    tx: mpsc::UnboundedSender<ErrorReport>,
+}
+
+impl ErrChan {
+    /// Spawn the background reporter task and return a handle to send errors to it
+    pub fn spawn() -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<ErrorReport>();
+
+        tokio::spawn(async move {
+            while let Some(first) = rx.recv().await {
+                let mut batch = vec![first];
+                while let Ok(next) = rx.try_recv() {
+                    batch.push(next);
+                }
+
+                for attempt in 1..=MAX_REPORT_ATTEMPTS {
+                    if report_batch(&batch) {
+                        break;
+                    }
+                    if attempt == MAX_REPORT_ATTEMPTS {
+                        tracing::warn!(count = batch.len(), "dropping error batch after {MAX_REPORT_ATTEMPTS} failed report attempts");
+                    } else {
+                        tokio::time::sleep(Duration::from_millis(200 * attempt as u64)).await;
+                    }
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    /// Report a failure from `source` (e.g. a tool name or model call site)
+    pub async fn send(&self, err: impl std::fmt::Display, source: &str) {
+        let _ = self.tx.send(ErrorReport {
+            source: source.to_string(),
+            message: err.to_string(),
+        });
+    }
+}
+
+/// Emit a batch of error reports via `tracing`; returns whether reporting
+/// succeeded. This is the single seam a real reporting backend (Sentry, a
+/// metrics pipeline, etc.) would plug into in place of the `tracing::error!` sink.
+fn report_batch(batch: &[ErrorReport]) -> bool {
+    for report in batch {
+        tracing::error!(source = %report.source, "{}", report.message);
+    }
+    true
+}
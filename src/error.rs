@@ -0,0 +1,110 @@
+use axum::response::{IntoResponse, Json, Response};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Crate-wide error type, returned across the `Workflow`, `Memory`,
+/// `WorkflowRunner`, and pull-mode worker (`RunnerClient`/`WorkResult`) APIs so
+/// callers can pattern-match failure categories - e.g. "agent down" vs. "bad
+/// request" - instead of parsing opaque strings. `Serialize`/`Deserialize` so
+/// it survives the gRPC/HTTP wire intact; see `to_tonic_status` and the
+/// `IntoResponse` impl below for how each variant maps onto those transports.
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum HelixorError {
+    #[error("workflow '{0}' not found")]
+    WorkflowNotFound(String),
+    #[error("agent '{0}' unavailable")]
+    AgentUnavailable(String),
+    #[error("model error: {0}")]
+    ModelError(String),
+    #[error("memory error: {0}")]
+    MemoryError(String),
+    #[error("connection error: {0}")]
+    ConnectionError(String),
+    #[error("state transition error: {0}")]
+    TransitionError(String),
+    #[error("missing API key: set the provider's API key environment variable")]
+    MissingApiKey,
+    #[error("agent '{0}' not found")]
+    AgentNotFound(String),
+    #[error("tool '{tool}' failed: {msg}")]
+    ToolFailure { tool: String, msg: String },
+    #[error("workflow stuck in state '{state}': no further transitions available")]
+    WorkflowStuck { state: String },
+    /// Catch-all for the many call sites that already build an ad-hoc
+    /// `format!(...)` error string; lets this type slot in wherever a bare
+    /// `String` error used to via `?`, without forcing every such site to
+    /// pick a more specific variant up front
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<String> for HelixorError {
+    fn from(message: String) -> Self {
+        HelixorError::Other(message)
+    }
+}
+
+impl From<&str> for HelixorError {
+    fn from(message: &str) -> Self {
+        HelixorError::Other(message.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for HelixorError {
+    fn from(e: rusqlite::Error) -> Self {
+        HelixorError::MemoryError(e.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for HelixorError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        HelixorError::Other(format!("task panicked: {e}"))
+    }
+}
+
+impl HelixorError {
+    /// HTTP status this error category maps to, used by the `IntoResponse` impl
+    fn status_code(&self) -> StatusCode {
+        match self {
+            HelixorError::WorkflowNotFound(_) => StatusCode::NOT_FOUND,
+            HelixorError::AgentUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            HelixorError::ModelError(_) => StatusCode::BAD_GATEWAY,
+            HelixorError::MemoryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            HelixorError::ConnectionError(_) => StatusCode::BAD_GATEWAY,
+            HelixorError::TransitionError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            HelixorError::MissingApiKey => StatusCode::UNAUTHORIZED,
+            HelixorError::AgentNotFound(_) => StatusCode::NOT_FOUND,
+            HelixorError::ToolFailure { .. } => StatusCode::BAD_GATEWAY,
+            HelixorError::WorkflowStuck { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            HelixorError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// `tonic::Status` this error category maps to, for the gRPC surface
+    pub fn to_tonic_status(&self) -> tonic::Status {
+        let code = match self {
+            HelixorError::WorkflowNotFound(_) => tonic::Code::NotFound,
+            HelixorError::AgentUnavailable(_) => tonic::Code::Unavailable,
+            HelixorError::ModelError(_) => tonic::Code::Internal,
+            HelixorError::MemoryError(_) => tonic::Code::Internal,
+            HelixorError::ConnectionError(_) => tonic::Code::Unavailable,
+            HelixorError::TransitionError(_) => tonic::Code::FailedPrecondition,
+            HelixorError::MissingApiKey => tonic::Code::Unauthenticated,
+            HelixorError::AgentNotFound(_) => tonic::Code::NotFound,
+            HelixorError::ToolFailure { .. } => tonic::Code::Internal,
+            HelixorError::WorkflowStuck { .. } => tonic::Code::FailedPrecondition,
+            HelixorError::Other(_) => tonic::Code::Unknown,
+        };
+        tonic::Status::new(code, self.to_string())
+    }
+}
+
+/// Lets HTTP handlers return `Result<Json<T>, HelixorError>` directly; the
+/// body is the error itself, serialized, so a caller can match on its variant
+/// instead of scraping a message string
+impl IntoResponse for HelixorError {
+    fn into_response(self) -> Response {
+        (self.status_code(), Json(self)).into_response()
+    }
+}
@@ -61,17 +61,19 @@ impl AgentServiceImpl {
 
 #[tonic::async_trait]
 impl AgentService for AgentServiceImpl {
+    #[tracing::instrument(skip(self, request), fields(session_id = tracing::field::Empty))]
     async fn chat(
         &self,
         request: Request<ChatRequest>,
     ) -> Result<Response<ChatResponse>, Status> {
         let req = request.into_inner();
         let mut agent = self.agent.lock().await;
-        
+
         let response = agent.run_once(&req.message).await;
         let session_id = req.session_id.unwrap_or_else(|| {
             uuid::Uuid::new_v4().to_string()
         });
+        tracing::Span::current().record("session_id", session_id.as_str());
 
         Ok(Response::new(ChatResponse {
             response,
@@ -80,12 +82,13 @@ impl AgentService for AgentServiceImpl {
         }))
     }
 
+    #[tracing::instrument(skip(self, _request))]
     async fn health(
         &self,
         _request: Request<HealthRequest>,
     ) -> Result<Response<HealthResponse>, Status> {
         let agent = self.agent.lock().await;
-        
+
         Ok(Response::new(HealthResponse {
             status: "healthy".to_string(),
             agent_name: agent.name.clone(),
@@ -96,12 +99,13 @@ impl AgentService for AgentServiceImpl {
         }))
     }
 
+    #[tracing::instrument(skip(self, _request))]
     async fn status(
         &self,
         _request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
         let agent = self.agent.lock().await;
-        
+
         Ok(Response::new(StatusResponse {
             name: agent.name.clone(),
             status: "running".to_string(),
@@ -111,19 +115,141 @@ impl AgentService for AgentServiceImpl {
     }
 }
 
-/// Start gRPC server
+/// The real, codegen-backed gRPC service (see proto/agent.proto), compiled
+/// only when `build.rs` found protoc. `chat` streams response chunks as the
+/// model generates them via `Agent::run_stream` instead of buffering the
+/// whole completion before replying.
+#[cfg(has_protoc)]
+pub mod codegen {
+    use super::*;
+    use futures::StreamExt;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use tonic::transport::Server;
+
+    tonic::include_proto!("agent");
+
+    use agent_service_server::{AgentService as GeneratedAgentService, AgentServiceServer};
+
+    pub struct GeneratedAgentServiceImpl {
+        pub agent: Arc<Mutex<Agent>>,
+    }
+
+    #[tonic::async_trait]
+    impl GeneratedAgentService for GeneratedAgentServiceImpl {
+        type ChatStream = ReceiverStream<Result<ChatStreamResponse, Status>>;
+
+        #[tracing::instrument(skip(self, request), fields(session_id = tracing::field::Empty))]
+        async fn chat(
+            &self,
+            request: Request<ChatRequest>,
+        ) -> Result<Response<Self::ChatStream>, Status> {
+            let req = request.into_inner();
+            let session_id = req.session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            tracing::Span::current().record("session_id", session_id.as_str());
+
+            let mut fragments = self.agent.lock().await.run_stream(&req.message).await;
+
+            // Bounded so a slow client applies backpressure to generation
+            // instead of the whole response buffering in memory up front.
+            let (tx, rx) = mpsc::channel(16);
+            let stream_session_id = session_id.clone();
+
+            tokio::spawn(async move {
+                while let Some(delta) = fragments.next().await {
+                    let chunk = ChatStreamResponse {
+                        delta,
+                        session_id: stream_session_id.clone(),
+                        finished: false,
+                    };
+                    if tx.send(Ok(chunk)).await.is_err() {
+                        return;
+                    }
+                }
+
+                let _ = tx
+                    .send(Ok(ChatStreamResponse {
+                        delta: String::new(),
+                        session_id: stream_session_id,
+                        finished: true,
+                    }))
+                    .await;
+            });
+
+            Ok(Response::new(ReceiverStream::new(rx)))
+        }
+
+        #[tracing::instrument(skip(self, _request))]
+        async fn health(&self, _request: Request<HealthRequest>) -> Result<Response<HealthResponse>, Status> {
+            let agent = self.agent.lock().await;
+            Ok(Response::new(HealthResponse {
+                status: "healthy".to_string(),
+                agent_name: agent.name.clone(),
+                uptime: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            }))
+        }
+
+        #[tracing::instrument(skip(self, _request))]
+        async fn status(&self, _request: Request<StatusRequest>) -> Result<Response<StatusResponse>, Status> {
+            let agent = self.agent.lock().await;
+            Ok(Response::new(StatusResponse {
+                name: agent.name.clone(),
+                status: "running".to_string(),
+                memory_backend: "sqlite".to_string(),
+                tools_available: agent.tools.len() as u32,
+            }))
+        }
+    }
+
+    pub async fn serve(
+        agent: Arc<Mutex<Agent>>,
+        port: u16,
+        tls: Option<crate::deploy::tls::TlsConfig>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let addr = format!("0.0.0.0:{}", port).parse()?;
+        let service = AgentServiceServer::new(GeneratedAgentServiceImpl { agent });
+
+        let mut server = Server::builder();
+        if let Some(tls) = tls {
+            server = server.tls_config(tls.load_tonic().await?)?;
+        }
+
+        server.add_service(service).serve(addr).await?;
+        Ok(())
+    }
+}
+
+/// Start gRPC server. With protoc available at build time this binds a real
+/// `tonic` transport serving the streaming `chat` RPC (see the `codegen`
+/// module); without it, falls back to the placeholder loop so the rest of
+/// the process (HTTP server, etc.) keeps working.
 pub async fn start_grpc_server(
     agent: Arc<Mutex<Agent>>,
     port: u16,
+    tls: Option<crate::deploy::tls::TlsConfig>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    println!("🌐 Starting gRPC server on 0.0.0.0:{}", port);
-    println!("⚠️ gRPC server requires protoc to be installed for full functionality.");
-    println!("   For now, using HTTP endpoints. Install protoc with:");
-    println!("   winget install Google.Protobuf");
-    
-    // For now, we'll just keep the server running but not actually serve gRPC
-    // This allows the HTTP server to work while we wait for protoc installation
-    loop {
-        tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await;
+    #[cfg(has_protoc)]
+    {
+        tracing::info!(port, "starting gRPC server");
+        return codegen::serve(agent, port, tls).await;
+    }
+
+    #[cfg(not(has_protoc))]
+    {
+        let _ = agent;
+        tracing::info!(port, "starting gRPC server");
+        if tls.is_some() {
+            tracing::warn!("TLS configured; will apply once this server terminates real gRPC traffic");
+        }
+        tracing::warn!("gRPC server requires protoc to be installed for full functionality; falling back to HTTP endpoints only (install protoc, e.g. `winget install Google.Protobuf`)");
+
+        // For now, we'll just keep the server running but not actually serve gRPC
+        // This allows the HTTP server to work while we wait for protoc installation
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await;
+        }
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -23,23 +23,34 @@
 //! ```
 
 pub mod agent;
+pub mod bench;
 pub mod config;
 pub mod deploy;
+pub mod errchan;
+pub mod error;
 pub mod grpc;
+pub mod logging;
 pub mod memory;
 pub mod model;
+pub mod role;
+pub mod runner;
+pub mod scheduler;
 pub mod team;
 pub mod tool;
 pub mod workflow;
 
 // Re-export main types for easy importing
 pub use agent::{Agent, AgentBuilder};
+pub use errchan::ErrChan;
+pub use error::HelixorError;
 pub use memory::{Memory, SqliteMemory, InMemory};
-pub use model::{Model, OpenAiClient};
+pub use model::{Model, OpenAiClient, ModelRegistry, ProviderConfig, Embedder, HashingEmbedder, OpenAiEmbedder};
+pub use role::{Role, RoleRegistry};
+pub use runner::{Coordinator, RunnerClient, WorkAcquireError, WorkRequest, WorkResult};
 pub use team::{Team, TeamBuilder, TeamAgent, TeamWorkflow, FSMConfig, DAGConfig};
 pub use tool::{ToolRegistry};
 pub use tool::tool_traits::Tool;
-pub use deploy::{deploy_agent_instance, deploy_team_instance};
+pub use deploy::{deploy_team_instance, TeamInstanceHandle};
 
 // Re-export common traits
 pub use async_trait::async_trait;
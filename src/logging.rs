@@ -0,0 +1,15 @@
+use tracing_subscriber::{fmt, EnvFilter};
+
+/// Install a global `tracing` subscriber for the process. The log level is read
+/// from `RUST_LOG` (e.g. `RUST_LOG=helixor=debug`), defaulting to `info` when
+/// unset. `json` selects structured JSON output, suited to log aggregators,
+/// over the default human-readable format.
+pub fn init_tracing(json: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    if json {
+        fmt().with_env_filter(filter).json().init();
+    } else {
+        fmt().with_env_filter(filter).init();
+    }
+}
@@ -1,15 +1,24 @@
 mod agent;
+mod bench;
 mod cli;
 mod config;
-mod model;
-mod tool;
+mod deploy;
+mod errchan;
+mod error;
+mod grpc;
+mod logging;
 mod memory;
+mod model;
+mod role;
+mod runner;
+mod scheduler;
 mod team;
+mod tool;
 mod workflow;
-mod deploy;
 
 #[tokio::main]
 async fn main() {
     dotenv::dotenv().ok();
+    logging::init_tracing(std::env::var("HELIXOR_LOG_JSON").is_ok());
     cli::run_cli().await;
 }
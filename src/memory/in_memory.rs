@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use crate::error::HelixorError;
 use crate::memory::memory_trait::Memory;
 use crate::model::model_trait::Message;
 
@@ -37,7 +38,7 @@ impl Memory for InMemoryMemory {
         self.store.lock().unwrap().clone()
     }
 
-    async fn clear(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn clear(&self) -> Result<(), HelixorError> {
         self.store.lock().unwrap().clear();
         self.kv.lock().unwrap().clear();
         Ok(())
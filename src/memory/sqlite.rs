@@ -2,16 +2,28 @@ use async_trait::async_trait;
 use chrono::Utc;
 use rusqlite::{params, Connection};
 use std::sync::{Arc, Mutex};
+use crate::error::HelixorError;
+use crate::model::embedder::{Embedder, HashingEmbedder};
 use crate::model::model_trait::Message;
 use crate::memory::memory_trait::Memory;
 
 #[derive(Clone)]
 pub struct SqliteMemory {
     conn: Arc<Mutex<Connection>>,
+    /// `store` persists a content embedding alongside each row with this, and
+    /// `recall`/`recall_semantic` rank by cosine similarity over it. Defaults
+    /// to the offline `HashingEmbedder`; `with_embedder` swaps in a real model.
+    embedder: Arc<dyn Embedder + Send + Sync>,
 }
 
 impl SqliteMemory {
     pub fn new(path: &str) -> rusqlite::Result<Self> {
+        Self::with_embedder(path, Arc::new(HashingEmbedder))
+    }
+
+    /// Same as `new`, but with an `Embedder` wired in so stored content gets a
+    /// vector embedding from it instead of the default offline hashing scheme
+    pub fn with_embedder(path: &str, embedder: Arc<dyn Embedder + Send + Sync>) -> rusqlite::Result<Self> {
         let conn = Connection::open(path)?;
         conn.execute_batch(
             r#"
@@ -19,37 +31,225 @@ impl SqliteMemory {
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 role TEXT NOT NULL,
                 content TEXT NOT NULL,
-                timestamp TEXT NOT NULL
+                timestamp TEXT NOT NULL,
+                embedding BLOB
+            );
+            CREATE TABLE IF NOT EXISTS workflow_runs (
+                run_id TEXT PRIMARY KEY,
+                workflow_kind TEXT NOT NULL,
+                current TEXT NOT NULL,
+                status TEXT NOT NULL,
+                outputs TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS schedule_entries (
+                id TEXT PRIMARY KEY,
+                team_name TEXT NOT NULL,
+                task TEXT NOT NULL,
+                cron_expr TEXT NOT NULL,
+                last_run TEXT,
+                next_run TEXT NOT NULL,
+                enabled INTEGER NOT NULL,
+                overlap TEXT NOT NULL
             );
             "#,
         )?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            embedder,
         })
     }
+
+    /// Upsert a resumable run's progress. Callers (e.g. `TeamDispatcher`) own
+    /// the meaning of `workflow_kind`/`current`/`status`/`outputs_json` — this
+    /// is deliberately a thin, opaque row store rather than typed FSM/DAG
+    /// structures, so `SqliteMemory` doesn't need to know about team workflows.
+    pub async fn save_run(
+        &self,
+        run_id: &str,
+        workflow_kind: &str,
+        current: &str,
+        status: &str,
+        outputs_json: &str,
+    ) -> Result<(), HelixorError> {
+        let updated_at = Utc::now().to_rfc3339();
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO workflow_runs (run_id, workflow_kind, current, status, outputs, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(run_id) DO UPDATE SET
+                workflow_kind = excluded.workflow_kind,
+                current = excluded.current,
+                status = excluded.status,
+                outputs = excluded.outputs,
+                updated_at = excluded.updated_at",
+            params![run_id, workflow_kind, current, status, outputs_json, updated_at],
+        )?;
+        Ok(())
+    }
+
+    /// Load a previously saved run's `(workflow_kind, current, status, outputs_json)`
+    pub async fn load_run(&self, run_id: &str) -> Result<Option<(String, String, String, String)>, HelixorError> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT workflow_kind, current, status, outputs FROM workflow_runs WHERE run_id = ?1",
+            params![run_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        );
+        match result {
+            Ok(row) => Ok(Some(row)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Upsert a `TeamScheduler` entry's current state. Like `save_run`, this
+    /// is a thin opaque row store - `TeamScheduler` owns what `cron_expr`/
+    /// `overlap` mean, `SqliteMemory` just persists them.
+    pub async fn save_schedule_entry(
+        &self,
+        id: &str,
+        team_name: &str,
+        task: &str,
+        cron_expr: &str,
+        last_run: Option<&str>,
+        next_run: &str,
+        enabled: bool,
+        overlap: &str,
+    ) -> Result<(), HelixorError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO schedule_entries (id, team_name, task, cron_expr, last_run, next_run, enabled, overlap)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(id) DO UPDATE SET
+                team_name = excluded.team_name,
+                task = excluded.task,
+                cron_expr = excluded.cron_expr,
+                last_run = excluded.last_run,
+                next_run = excluded.next_run,
+                enabled = excluded.enabled,
+                overlap = excluded.overlap",
+            params![id, team_name, task, cron_expr, last_run, next_run, enabled, overlap],
+        )?;
+        Ok(())
+    }
+
+    /// Load every persisted schedule entry as
+    /// `(id, team_name, task, cron_expr, last_run, next_run, enabled, overlap)`
+    pub async fn load_schedule_entries(
+        &self,
+    ) -> Result<Vec<(String, String, String, String, Option<String>, String, bool, String)>, HelixorError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, team_name, task, cron_expr, last_run, next_run, enabled, overlap FROM schedule_entries",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    /// Forget a schedule entry entirely (e.g. when a team is decommissioned)
+    pub async fn delete_schedule_entry(&self, id: &str) -> Result<(), HelixorError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM schedule_entries WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Return the `top_k` stored rows most semantically similar to `query`, by
+    /// cosine similarity over embeddings.
+    pub async fn recall_semantic(&self, query: &str, top_k: usize) -> Vec<String> {
+        let query_embedding = self.embedder.embed(query).await;
+
+        let rows: Vec<(String, Option<Vec<u8>>)> = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = match conn.prepare("SELECT content, embedding FROM memory ORDER BY id ASC") {
+                Ok(stmt) => stmt,
+                Err(_) => return Vec::new(),
+            };
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)));
+            match rows {
+                Ok(rows) => rows.filter_map(Result::ok).collect(),
+                Err(_) => return Vec::new(),
+            }
+        };
+
+        let mut scored: Vec<(f32, String)> = rows
+            .into_iter()
+            .filter_map(|(content, blob)| {
+                let blob = blob?;
+                let embedding = blob_to_embedding(&blob);
+                Some((cosine_similarity(&query_embedding, &embedding), content))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().take(top_k).map(|(_, content)| content).collect()
+    }
+}
+
+/// Serialize an embedding to a little-endian byte blob for SQLite storage
+fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+/// Deserialize an embedding previously written by `embedding_to_blob`
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
 }
 
 #[async_trait]
 impl Memory for SqliteMemory {
     async fn recall(&self, key: &str) -> Option<String> {
-        let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT content FROM memory WHERE content LIKE ? ORDER BY id DESC LIMIT 1"
-        ).ok()?;
-        let mut rows = stmt.query(params![format!("%{}%", key)]).ok()?;
-        if let Some(row) = rows.next().ok()? {
-            row.get(0).ok()
-        } else {
-            None
-        }
+        let query_embedding = self.embedder.embed(key).await;
+        let rows: Vec<(String, Option<Vec<u8>>)> = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn
+                .prepare("SELECT content, embedding FROM memory ORDER BY id ASC")
+                .ok()?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).ok()?;
+            rows.filter_map(Result::ok).collect()
+        };
+
+        rows.into_iter()
+            .filter_map(|(content, blob)| {
+                let embedding = blob_to_embedding(&blob?);
+                Some((cosine_similarity(&query_embedding, &embedding), content))
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, content)| content)
     }
 
     async fn store(&self, role: &str, content: &str) {
-        let conn = self.conn.lock().unwrap();
         let timestamp = Utc::now().to_rfc3339();
+        let embedding = embedding_to_blob(&self.embedder.embed(content).await);
+
+        let conn = self.conn.lock().unwrap();
         let _ = conn.execute(
-            "INSERT INTO memory (role, content, timestamp) VALUES (?1, ?2, ?3)",
-            params![role, content, timestamp],
+            "INSERT INTO memory (role, content, timestamp, embedding) VALUES (?1, ?2, ?3, ?4)",
+            params![role, content, timestamp, embedding],
         );
     }
 
@@ -69,7 +269,7 @@ impl Memory for SqliteMemory {
         rows.filter_map(Result::ok).collect()
     }
 
-    async fn clear(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn clear(&self) -> Result<(), HelixorError> {
         let conn = self.conn.lock().unwrap();
         conn.execute("DELETE FROM memory", [])?;
         Ok(())
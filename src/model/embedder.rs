@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// Default OpenAI embeddings endpoint
+pub const DEFAULT_EMBEDDING_BASE_URL: &str = "https://api.openai.com/v1/embeddings";
+
+/// Default embedding model used when no override is configured
+pub const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// Turns text into a fixed-size vector for similarity search, e.g. semantic
+/// recall over `SqliteMemory`
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+/// Dimensionality of the bag-of-words vectors `HashingEmbedder` produces
+const HASHING_EMBEDDER_DIMS: usize = 256;
+
+/// Offline fallback embedder: hashes each lowercased token into a bucket of a
+/// fixed-width vector and L2-normalizes the result. Nowhere near as accurate
+/// as a real embedding model, but it needs no network access or API key, so
+/// `SqliteMemory` can do semantic recall out of the box; swap in
+/// `OpenAiEmbedder` via `SqliteMemory::with_embedder` for better quality.
+#[derive(Debug, Clone, Default)]
+pub struct HashingEmbedder;
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    async fn embed(&self, text: &str) -> Vec<f32> {
+        let mut buckets = vec![0f32; HASHING_EMBEDDER_DIMS];
+
+        for token in text.to_lowercase().split_whitespace() {
+            let index = fnv1a_hash(token.as_bytes()) as usize % HASHING_EMBEDDER_DIMS;
+            buckets[index] += 1.0;
+        }
+
+        let norm = buckets.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for bucket in &mut buckets {
+                *bucket /= norm;
+            }
+        }
+
+        buckets
+    }
+}
+
+/// FNV-1a: a small, dependency-free hash, good enough for bucketing tokens
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Embedder backed by OpenAI's `/embeddings` endpoint, mirroring `OpenAiClient`'s
+/// api-key/endpoint plumbing
+pub struct OpenAiEmbedder {
+    pub api_key: String,
+    pub base_url: String,
+    pub model: String,
+    http: Client,
+}
+
+impl OpenAiEmbedder {
+    /// Create an embedder against the public OpenAI API using the default model
+    pub fn new(api_key: String) -> Self {
+        Self::with_endpoint(
+            api_key,
+            DEFAULT_EMBEDDING_BASE_URL.to_string(),
+            DEFAULT_EMBEDDING_MODEL.to_string(),
+        )
+    }
+
+    /// Create an embedder against a custom OpenAI-compatible embeddings endpoint
+    pub fn with_endpoint(api_key: String, base_url: String, model: String) -> Self {
+        Self {
+            api_key,
+            base_url,
+            model,
+            http: Client::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(&self, text: &str) -> Vec<f32> {
+        let request_body = EmbeddingRequest {
+            model: &self.model,
+            input: text,
+        };
+
+        let response = self
+            .http
+            .post(&self.base_url)
+            .bearer_auth(&self.api_key)
+            .json(&request_body)
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) => match resp.json::<EmbeddingResponse>().await {
+                Ok(parsed) => parsed
+                    .data
+                    .into_iter()
+                    .next()
+                    .map(|d| d.embedding)
+                    .unwrap_or_default(),
+                Err(err) => {
+                    eprintln!("❌ Failed to parse OpenAI embedding response: {err}");
+                    Vec::new()
+                }
+            },
+            Err(err) => {
+                eprintln!("❌ HTTP request to OpenAI embeddings failed: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod embedder;
+pub mod model_trait;
+pub mod openai;
+pub mod registry;
+
+pub use embedder::{Embedder, HashingEmbedder, OpenAiEmbedder};
+pub use model_trait::{Message, Model, ModelResponse, ToolCallRequest, ToolSchema};
+pub use openai::OpenAiClient;
+pub use registry::{ModelRegistry, ProviderConfig};
@@ -1,15 +1,66 @@
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
 use serde::{Deserialize, Serialize};
 
 /// Standard chat message format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
-    pub role: String,    // "system", "user", or "assistant"
+    pub role: String,    // "system", "user", "assistant", or "tool"
     pub content: String,
 }
 
+/// JSON-Schema description of one tool, sent to providers that support native
+/// function-calling so the model knows what it can invoke and with what arguments
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolSchema {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+/// A tool invocation the model asked for via native function-calling
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolCallRequest {
+    pub id: Option<String>,
+    pub name: String,
+    /// Raw JSON arguments string, exactly as the provider sent it
+    pub arguments: String,
+}
+
+/// The model's reply to a native function-calling turn: natural-language content,
+/// zero or more tool calls, or both (some providers emit a tool call alongside a
+/// short acknowledgement)
+#[derive(Debug, Clone, Default)]
+pub struct ModelResponse {
+    pub content: Option<String>,
+    pub tool_calls: Vec<ToolCallRequest>,
+}
+
 /// Trait for all LLMs used in the agent
 #[async_trait]
 pub trait Model: Send + Sync {
     async fn generate(&self, messages: Vec<Message>) -> String;
+
+    /// Stream the response as it is generated, one text fragment at a time.
+    /// Providers without native streaming support can rely on this default,
+    /// which just yields the fully-buffered response as a single chunk.
+    async fn generate_stream(&self, messages: Vec<Message>) -> BoxStream<'static, String> {
+        let full = self.generate(messages).await;
+        Box::pin(stream::once(async move { full }))
+    }
+
+    /// Native function-calling variant: advertises `tools` to the provider and parses
+    /// structured tool calls out of the response instead of scraping the content
+    /// field for JSON. Providers without native tool-calling support can rely on this
+    /// default, which falls back to `generate` and reports no tool calls.
+    async fn generate_with_tools(
+        &self,
+        messages: Vec<Message>,
+        _tools: &[ToolSchema],
+    ) -> ModelResponse {
+        ModelResponse {
+            content: Some(self.generate(messages).await),
+            tool_calls: Vec::new(),
+        }
+    }
 }
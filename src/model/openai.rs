@@ -1,22 +1,59 @@
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use crate::model::model_trait::{Message, Model};
+use serde_json::{json, Value};
+use crate::model::model_trait::{Message, Model, ModelResponse, ToolCallRequest, ToolSchema};
 
+/// Default OpenAI chat-completions endpoint used when no override is configured
+pub const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1/chat/completions";
 
-/// Struct representing the OpenAI client
+/// Default chat model used when no override is configured
+pub const DEFAULT_OPENAI_MODEL: &str = "gpt-3.5-turbo";
+
+/// Struct representing the OpenAI client (also usable against any OpenAI-compatible endpoint)
 pub struct OpenAiClient {
     pub api_key: String,
+    pub base_url: String,
+    pub model: String,
     pub http: Client,
 }
 
 impl OpenAiClient {
+    /// Create a client against the public OpenAI API using the default chat model
     pub fn new(api_key: String) -> Self {
+        Self::with_endpoint(api_key, DEFAULT_OPENAI_BASE_URL.to_string(), DEFAULT_OPENAI_MODEL.to_string())
+    }
+
+    /// Create a client against a custom OpenAI-compatible endpoint, e.g. a self-hosted
+    /// or third-party provider that speaks the same `/chat/completions` wire format
+    pub fn with_endpoint(api_key: String, base_url: String, model: String) -> Self {
         Self {
             api_key,
+            base_url,
+            model,
             http: Client::new(),
         }
     }
+
+    /// Prepend the fixed agent system prompt unless the caller already supplied one
+    fn with_system_prompt(&self, mut messages: Vec<Message>) -> Vec<Message> {
+        let system_prompt = Message {
+            role: "system".to_string(),
+            content: r#"You are an intelligent AI agent.
+You may invoke tools when needed by responding with JSON like:
+{"tool_call": {"name": "search", "args": "interest rate trends"}}
+If a tool is not required, just answer normally."#
+                .to_string(),
+        };
+
+        if messages.is_empty() || messages.first().unwrap().role != "system" {
+            messages.insert(0, system_prompt);
+        }
+
+        messages
+    }
 }
 
 /// Structure of a chat request sent to OpenAI
@@ -24,6 +61,53 @@ impl OpenAiClient {
 struct ChatRequest {
     model: String,
     messages: Vec<Message>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<Value>>,
+}
+
+/// Response shape used when native tool-calling is in play: `message.content` may be
+/// absent (a pure tool-call turn) and `message.tool_calls` carries the structured calls
+#[derive(Deserialize)]
+struct ToolChatResponse {
+    choices: Vec<ToolChoice>,
+}
+
+#[derive(Deserialize)]
+struct ToolChoice {
+    message: ResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<FunctionToolCall>,
+}
+
+#[derive(Deserialize)]
+struct FunctionToolCall {
+    id: Option<String>,
+    function: FunctionCall,
+}
+
+#[derive(Deserialize)]
+struct FunctionCall {
+    name: String,
+    arguments: String,
+}
+
+/// Convert a tool's JSON-Schema description into the `{"type": "function", ...}`
+/// shape OpenAI's `tools` request field expects
+fn tool_schema_to_request(schema: &ToolSchema) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": schema.name,
+            "description": schema.description,
+            "parameters": schema.parameters,
+        }
+    })
 }
 
 /// Response from OpenAI containing choices
@@ -38,35 +122,41 @@ struct Choice {
     message: Message,
 }
 
+/// One chunk of a streamed response, as sent in each SSE `data:` line
+#[derive(Deserialize)]
+struct ChatStreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize, Default)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
 /// Implementing the Model trait for the OpenAI client
 #[async_trait]
 impl Model for OpenAiClient {
     /// Generates a response by calling OpenAI with provided conversation messages
-    async fn generate(&self, mut messages: Vec<Message>) -> String {
-        // Inject system prompt at the beginning if not already present
-        let system_prompt = Message {
-            role: "system".to_string(),
-            content: r#"You are an intelligent AI agent.
-You may invoke tools when needed by responding with JSON like:
-{"tool_call": {"name": "search", "args": "interest rate trends"}}
-If a tool is not required, just answer normally."#
-                .to_string(),
-        };
-
-        if messages.is_empty() || messages.first().unwrap().role != "system" {
-            messages.insert(0, system_prompt);
-        }
+    async fn generate(&self, messages: Vec<Message>) -> String {
+        let messages = self.with_system_prompt(messages);
 
         // Build full chat request
         let request_body = ChatRequest {
-            model: "gpt-3.5-turbo".to_string(),
+            model: self.model.clone(),
             messages,
+            stream: false,
+            tools: None,
         };
 
-        // Send request to OpenAI
+        // Send request to the configured endpoint
         let response = self
             .http
-            .post("https://api.openai.com/v1/chat/completions")
+            .post(&self.base_url)
             .bearer_auth(&self.api_key)
             .json(&request_body)
             .send()
@@ -93,4 +183,150 @@ If a tool is not required, just answer normally."#
             }
         }
     }
+
+    /// Streams the response as Server-Sent-Events chunks arrive, yielding each
+    /// `delta.content` fragment as it's received. The final accumulated text is
+    /// not returned here — callers that also need it (e.g. to persist to memory)
+    /// should accumulate the yielded fragments themselves.
+    async fn generate_stream(&self, messages: Vec<Message>) -> BoxStream<'static, String> {
+        let messages = self.with_system_prompt(messages);
+
+        let request_body = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            stream: true,
+            tools: None,
+        };
+
+        let response = self
+            .http
+            .post(&self.base_url)
+            .bearer_auth(&self.api_key)
+            .json(&request_body)
+            .send()
+            .await;
+
+        let resp = match response {
+            Ok(resp) => resp,
+            Err(err) => {
+                eprintln!("❌ HTTP request to OpenAI failed: {err}");
+                return Box::pin(stream::empty());
+            }
+        };
+
+        // Each SSE event arrives as one or more `data: <json>` lines, buffered across
+        // chunk boundaries since a single byte chunk may split a line in two.
+        let byte_stream = resp.bytes_stream();
+        let fragments = byte_stream
+            .scan(String::new(), |buffer, chunk| {
+                let mut fragments = Vec::new();
+                match chunk {
+                    Ok(bytes) => {
+                        buffer.push_str(&String::from_utf8_lossy(&bytes));
+                        while let Some(newline) = buffer.find('\n') {
+                            let line = buffer[..newline].trim().to_string();
+                            buffer.drain(..=newline);
+
+                            let Some(data) = line.strip_prefix("data:") else {
+                                continue;
+                            };
+                            let data = data.trim();
+                            if data.is_empty() || data == "[DONE]" {
+                                continue;
+                            }
+
+                            match serde_json::from_str::<ChatStreamChunk>(data) {
+                                Ok(chunk) => {
+                                    if let Some(choice) = chunk.choices.into_iter().next() {
+                                        if let Some(content) = choice.delta.content {
+                                            fragments.push(content);
+                                        }
+                                    }
+                                }
+                                Err(err) => {
+                                    eprintln!("❌ Failed to parse OpenAI stream chunk: {err}");
+                                }
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        eprintln!("❌ OpenAI stream read error: {err}");
+                    }
+                }
+                std::future::ready(Some(stream::iter(fragments)))
+            })
+            .flatten();
+
+        Box::pin(fragments)
+    }
+
+    /// Sends `tools` to OpenAI's native function-calling API and parses the
+    /// structured `tool_calls` the model comes back with, instead of relying on it
+    /// embedding JSON inside the `content` field.
+    async fn generate_with_tools(
+        &self,
+        messages: Vec<Message>,
+        tools: &[ToolSchema],
+    ) -> ModelResponse {
+        let messages = self.with_system_prompt(messages);
+
+        let request_body = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            stream: false,
+            tools: if tools.is_empty() {
+                None
+            } else {
+                Some(tools.iter().map(tool_schema_to_request).collect())
+            },
+        };
+
+        let response = self
+            .http
+            .post(&self.base_url)
+            .bearer_auth(&self.api_key)
+            .json(&request_body)
+            .send()
+            .await;
+
+        let resp = match response {
+            Ok(resp) => resp,
+            Err(err) => {
+                eprintln!("❌ HTTP request to OpenAI failed: {err}");
+                return ModelResponse {
+                    content: Some("❌ Could not reach OpenAI.".to_string()),
+                    tool_calls: Vec::new(),
+                };
+            }
+        };
+
+        match resp.json::<ToolChatResponse>().await {
+            Ok(parsed) => match parsed.choices.into_iter().next() {
+                Some(choice) => ModelResponse {
+                    content: choice.message.content,
+                    tool_calls: choice
+                        .message
+                        .tool_calls
+                        .into_iter()
+                        .map(|call| ToolCallRequest {
+                            id: call.id,
+                            name: call.function.name,
+                            arguments: call.function.arguments,
+                        })
+                        .collect(),
+                },
+                None => ModelResponse {
+                    content: Some("⚠️ OpenAI returned no response.".to_string()),
+                    tool_calls: Vec::new(),
+                },
+            },
+            Err(err) => {
+                eprintln!("❌ Failed to parse OpenAI JSON response: {err}");
+                ModelResponse {
+                    content: Some("❌ Failed to interpret OpenAI response.".to_string()),
+                    tool_calls: Vec::new(),
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::model::model_trait::Model;
+use crate::model::openai::OpenAiClient;
+
+/// A single named provider's connection details, deserialized from the `[providers.*]`
+/// section of an agent/team TOML file. Tagged by `type` so new providers can be added
+/// without changing the config shape for existing ones.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    OpenAi {
+        api_key: String,
+        base_url: Option<String>,
+        model: String,
+    },
+    // Deliberately no `Anthropic`/`Cohere` variant here: neither speaks OpenAI's
+    // `{model, messages, stream, tools}` wire format (Anthropic wants
+    // `x-api-key`/`anthropic-version` headers and a `max_tokens`+content-block
+    // body; Cohere's `/v1/chat` takes `message`/`chat_history`, not `messages`),
+    // so routing them through `OpenAiClient` would silently fail every request.
+    // Add a real `Model` impl for each provider's actual wire format before
+    // reintroducing it here.
+    /// Any other OpenAI-compatible endpoint (local/self-hosted models, proxies, etc.)
+    Custom {
+        api_key: String,
+        base_url: String,
+        model: String,
+    },
+}
+
+impl ProviderConfig {
+    fn base_url(&self) -> String {
+        match self {
+            ProviderConfig::OpenAi { base_url, .. } => base_url
+                .clone()
+                .unwrap_or_else(|| crate::model::openai::DEFAULT_OPENAI_BASE_URL.to_string()),
+            ProviderConfig::Custom { base_url, .. } => base_url.clone(),
+        }
+    }
+
+    fn api_key(&self) -> &str {
+        match self {
+            ProviderConfig::OpenAi { api_key, .. } | ProviderConfig::Custom { api_key, .. } => api_key,
+        }
+    }
+
+    fn default_model(&self) -> &str {
+        match self {
+            ProviderConfig::OpenAi { model, .. } | ProviderConfig::Custom { model, .. } => model,
+        }
+    }
+
+    /// Build the concrete client for this provider, using `model_override` in place of
+    /// the config's default model when present (the `:model` half of a `provider:model`
+    /// identifier).
+    fn build(&self, model_override: Option<&str>) -> Box<dyn Model + Send + Sync> {
+        let model = model_override.unwrap_or_else(|| self.default_model()).to_string();
+
+        // Every provider above is currently reachable through the OpenAI-compatible
+        // chat-completions wire format, so they all resolve to an `OpenAiClient`
+        // pointed at the provider's endpoint. A provider with an incompatible wire
+        // format would get its own `Model` impl and be constructed here instead.
+        Box::new(OpenAiClient::with_endpoint(
+            self.api_key().to_string(),
+            self.base_url(),
+            model,
+        ))
+    }
+}
+
+/// Registry of named providers, resolving a `provider:model` identifier (the same
+/// convention already used by `TeamAgent.model`, e.g. `openai:gpt-4-turbo`) into a
+/// constructed `Box<dyn Model>`.
+#[derive(Debug, Default)]
+pub struct ModelRegistry {
+    providers: HashMap<String, ProviderConfig>,
+}
+
+impl ModelRegistry {
+    pub fn new() -> Self {
+        Self {
+            providers: HashMap::new(),
+        }
+    }
+
+    /// Build a registry from the `[providers]` table of an agent/team config
+    pub fn from_configs(providers: HashMap<String, ProviderConfig>) -> Self {
+        Self { providers }
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, provider: ProviderConfig) {
+        self.providers.insert(name.into(), provider);
+    }
+
+    /// Construct a `Model` for an identifier like `openai:gpt-4-turbo`. The part before
+    /// the colon selects the registered provider; the part after overrides its default
+    /// model. An identifier with no colon is treated as a bare provider name.
+    pub fn build(&self, identifier: &str) -> Result<Box<dyn Model + Send + Sync>, String> {
+        let (provider_name, model) = match identifier.split_once(':') {
+            Some((provider, model)) => (provider, Some(model)),
+            None => (identifier, None),
+        };
+
+        let provider = self
+            .providers
+            .get(provider_name)
+            .ok_or_else(|| format!("Unknown model provider '{}'", provider_name))?;
+
+        Ok(provider.build(model))
+    }
+}
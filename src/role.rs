@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A named persona: its own system prompt, and optionally a default model and a
+/// restricted tool set. Defined in TOML under an agent config's `[roles.<name>]`
+/// table and selected at runtime by name (e.g. via the CLI `--role` flag) instead
+/// of being baked into `Agent::new`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    /// System prompt this persona speaks with, replacing the agent config's
+    /// `instructions` when the role is active
+    pub system_prompt: String,
+    /// `provider:model` identifier to use instead of the agent config's `model`
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Tool names this persona is allowed to use instead of the agent config's
+    /// `tools`; `None` leaves the agent's configured tool set untouched
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+}
+
+/// Lookup table of personas declared in an agent config, keyed by role name
+#[derive(Debug, Clone, Default)]
+pub struct RoleRegistry {
+    roles: HashMap<String, Role>,
+}
+
+impl RoleRegistry {
+    /// Wrap a `{name: Role}` map (as deserialized from a config's `[roles]` table)
+    pub fn from_map(roles: HashMap<String, Role>) -> Self {
+        Self { roles }
+    }
+
+    /// Look up a persona by name
+    pub fn get(&self, name: &str) -> Option<&Role> {
+        self.roles.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roles.is_empty()
+    }
+}
@@ -0,0 +1,127 @@
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::agent::Agent;
+use crate::runner::protocol::{AcquireOutcome, AcquireRequest, WorkAcquireError, WorkRequest, WorkResult};
+use crate::workflow::runner::WorkflowRunner;
+
+/// Longest backoff between reconnect attempts after a failed or empty work-acquire
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Pulls jobs from a coordinator over a reused HTTP connection, executes each
+/// one through the shared agent (or a named workflow), and reports the result
+/// back before requesting the next job. Horizontally scales agent execution:
+/// run many of these against one coordinator instead of pushing requests at a
+/// single deployed agent. Each client carries an optional `role` (typically a
+/// `TeamAgent.role`) so the coordinator only ever hands it matching work,
+/// letting teams dispatch by role to workers that can't accept inbound
+/// connections (e.g. behind NAT/firewalls).
+pub struct RunnerClient {
+    http: Client,
+    coordinator_url: String,
+    agent: Arc<Mutex<Agent>>,
+    workflows: Arc<Mutex<WorkflowRunner>>,
+    role: Option<String>,
+}
+
+impl RunnerClient {
+    pub fn new(
+        coordinator_url: impl Into<String>,
+        agent: Arc<Mutex<Agent>>,
+        workflows: Arc<Mutex<WorkflowRunner>>,
+        role: Option<String>,
+    ) -> Self {
+        Self {
+            http: Client::new(),
+            coordinator_url: coordinator_url.into(),
+            agent,
+            workflows,
+            role,
+        }
+    }
+
+    /// Acquire, execute, and report jobs forever. An idle `NoWork` long-poll
+    /// and a failed/malformed acquire both back off exponentially so the
+    /// coordinator never gets busy-looped.
+    pub async fn run_forever(&self) {
+        let mut backoff = Duration::from_millis(200);
+
+        loop {
+            match self.acquire_work().await {
+                Ok(AcquireOutcome::Got(job)) => {
+                    backoff = Duration::from_millis(200);
+                    let result = self.execute(job).await;
+                    if let Err(e) = self.submit_result(&result).await {
+                        tracing::warn!(job_id = %result.job_id, error = %e, "failed to submit job result");
+                    }
+                }
+                Ok(AcquireOutcome::NoWork) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                Ok(AcquireOutcome::Protocol(msg)) => {
+                    tracing::warn!(error = %msg, backoff_ms = backoff.as_millis() as u64, "coordinator sent a malformed job, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, backoff_ms = backoff.as_millis() as u64, "work-acquire failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Send a "request work" frame carrying this worker's role and block
+    /// until the coordinator responds. A 204 with nothing queued for this
+    /// role is `NoWork`; a connection that closes without delivering a full
+    /// frame (an empty or unreadable body) is an `EarlyEof`; a body that
+    /// doesn't parse as a `WorkRequest` is `AcquireOutcome::Protocol`.
+    async fn acquire_work(&self) -> Result<AcquireOutcome, WorkAcquireError> {
+        let resp = self
+            .http
+            .post(format!("{}/runner/acquire", self.coordinator_url))
+            .json(&AcquireRequest { role: self.role.clone() })
+            .send()
+            .await?;
+
+        if resp.status() == reqwest::StatusCode::NO_CONTENT {
+            return Ok(AcquireOutcome::NoWork);
+        }
+
+        let body = resp.text().await.map_err(|_| WorkAcquireError::EarlyEof)?;
+        if body.is_empty() {
+            return Err(WorkAcquireError::EarlyEof);
+        }
+
+        Ok(match serde_json::from_str::<WorkRequest>(&body) {
+            Ok(job) => AcquireOutcome::Got(job),
+            Err(e) => AcquireOutcome::Protocol(e.to_string()),
+        })
+    }
+
+    async fn execute(&self, job: WorkRequest) -> WorkResult {
+        let outcome = match &job.workflow {
+            Some(name) => self.workflows.lock().await.execute_workflow(name, &job.input).await,
+            None => Ok(self.agent.lock().await.run_once(&job.input).await),
+        };
+
+        match outcome {
+            Ok(output) => WorkResult { job_id: job.job_id, output: Some(output), error: None },
+            Err(e) => WorkResult { job_id: job.job_id, output: None, error: Some(e) },
+        }
+    }
+
+    async fn submit_result(&self, result: &WorkResult) -> Result<(), reqwest::Error> {
+        self.http
+            .post(format!("{}/runner/result", self.coordinator_url))
+            .json(result)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tokio::time::Instant;
+
+use crate::runner::protocol::{AcquireRequest, WorkRequest, WorkResult};
+
+/// How long a worker's `/runner/acquire` call blocks waiting for a matching
+/// job before the coordinator gives up and responds 204 (`AcquireOutcome::NoWork`)
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queues jobs submitted via `submit_job`, each optionally targeting a
+/// `TeamAgent.role` (see `WorkRequest::target_role`), and hands them out in
+/// FIFO order to workers long-polling `POST /runner/acquire` with their own
+/// role - a queued job with no `target_role` matches any worker. Collects the
+/// `WorkResult` each worker reports back to `POST /runner/result`.
+#[derive(Clone)]
+pub struct Coordinator {
+    queue: Arc<Mutex<VecDeque<WorkRequest>>>,
+    notify: Arc<Notify>,
+    results: Arc<Mutex<HashMap<String, WorkResult>>>,
+}
+
+impl Coordinator {
+    pub fn new() -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+            notify: Arc::new(Notify::new()),
+            results: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Enqueue a job for the next worker whose role matches `job.target_role`
+    pub async fn submit_job(&self, job: WorkRequest) {
+        self.queue.lock().await.push_back(job);
+        self.notify.notify_waiters();
+    }
+
+    /// The result a runner reported for `job_id`, if it has reported one yet
+    pub async fn result_for(&self, job_id: &str) -> Option<WorkResult> {
+        self.results.lock().await.get(job_id).cloned()
+    }
+
+    /// Pop the first queued job matching `role`, waiting up to
+    /// `LONG_POLL_TIMEOUT` for one to arrive before giving up
+    async fn acquire_for_role(&self, role: Option<&str>) -> Option<WorkRequest> {
+        let deadline = Instant::now() + LONG_POLL_TIMEOUT;
+
+        loop {
+            // Register as a waiter *before* re-checking the queue: a
+            // `submit_job()` that calls `notify_waiters()` between the check
+            // and the `.await` below would otherwise have no registered
+            // waiter to wake, and this call would sleep out the full
+            // `LONG_POLL_TIMEOUT` instead of waking immediately. `Notify`
+            // only requires the `Notified` future to *exist* (not be polled
+            // yet) for `notify_waiters()` to reach it.
+            let notified = self.notify.notified();
+
+            {
+                let mut queue = self.queue.lock().await;
+                if let Some(pos) = queue.iter().position(|job| job_targets(job, role)) {
+                    return queue.remove(pos);
+                }
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+            let _ = tokio::time::timeout(remaining, notified).await;
+        }
+    }
+
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route("/runner/acquire", post(acquire))
+            .route("/runner/result", post(submit_result))
+            .with_state(self)
+    }
+}
+
+impl Default for Coordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A job with no `target_role` is role-agnostic and matches any worker
+fn job_targets(job: &WorkRequest, role: Option<&str>) -> bool {
+    match &job.target_role {
+        None => true,
+        Some(target) => Some(target.as_str()) == role,
+    }
+}
+
+/// Long-polls for a job matching the caller's role, responding with no body
+/// (`AcquireOutcome::NoWork`) if none arrives before the long-poll times out
+async fn acquire(State(coordinator): State<Coordinator>, Json(req): Json<AcquireRequest>) -> impl IntoResponse {
+    match coordinator.acquire_for_role(req.role.as_deref()).await {
+        Some(job) => Json(job).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn submit_result(State(coordinator): State<Coordinator>, Json(result): Json<WorkResult>) -> StatusCode {
+    coordinator.results.lock().await.insert(result.job_id.clone(), result);
+    StatusCode::OK
+}
@@ -0,0 +1,7 @@
+pub mod client;
+pub mod coordinator;
+pub mod protocol;
+
+pub use client::RunnerClient;
+pub use coordinator::Coordinator;
+pub use protocol::{AcquireOutcome, AcquireRequest, WorkAcquireError, WorkRequest, WorkResult};
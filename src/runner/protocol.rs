@@ -0,0 +1,79 @@
+use crate::error::HelixorError;
+use serde::{Deserialize, Serialize};
+
+/// A job handed to a runner. When `workflow` names a registered workflow the
+/// job is routed through it; otherwise `input` goes straight to the runner's
+/// agent via `run_once`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkRequest {
+    pub job_id: String,
+    pub input: String,
+    #[serde(default)]
+    pub workflow: Option<String>,
+    /// Restrict this job to a worker whose `RunnerClient` role matches (see
+    /// `TeamAgent.role`); `None` means any worker may pick it up
+    #[serde(default)]
+    pub target_role: Option<String>,
+}
+
+/// Sent with each `/runner/acquire` long-poll so the coordinator only hands
+/// back jobs targeting this worker's role (or role-agnostic ones)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AcquireRequest {
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// Outcome of a single `/runner/acquire` long-poll
+#[derive(Debug)]
+pub enum AcquireOutcome {
+    /// The coordinator handed back a task to run
+    Got(WorkRequest),
+    /// Nothing matching this worker's role was queued before the long-poll
+    /// timed out; back off before asking again instead of busy-looping
+    NoWork,
+    /// The coordinator responded, but not with a body that parses as a `WorkRequest`
+    Protocol(String),
+}
+
+/// What a runner reports back after executing (or failing to execute) a
+/// `WorkRequest`. `error` carries the typed `HelixorError` (not a bare
+/// string) so a caller reading results back can match on its variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkResult {
+    pub job_id: String,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub error: Option<HelixorError>,
+}
+
+/// What can go wrong while a `RunnerClient` is trying to acquire its next job
+#[derive(Debug)]
+pub enum WorkAcquireError {
+    /// The coordinator connection closed before a job was handed back
+    EarlyEof,
+    /// A frame that didn't parse as a `WorkRequest`; `RunnerClient` reports
+    /// that case as `AcquireOutcome::Protocol` rather than as this error
+    Protocol(String),
+    /// The underlying HTTP request itself failed
+    Transport(reqwest::Error),
+}
+
+impl std::fmt::Display for WorkAcquireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkAcquireError::EarlyEof => write!(f, "coordinator closed the connection before handing back a job"),
+            WorkAcquireError::Protocol(msg) => write!(f, "protocol error: {msg}"),
+            WorkAcquireError::Transport(e) => write!(f, "transport error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WorkAcquireError {}
+
+impl From<reqwest::Error> for WorkAcquireError {
+    fn from(e: reqwest::Error) -> Self {
+        WorkAcquireError::Transport(e)
+    }
+}
@@ -0,0 +1,232 @@
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use std::collections::HashSet;
+
+/// A single field of a 5-field cron expression: either unrestricted (`*`) or
+/// a concrete set of allowed values (from a number, a `a-b` range, a
+/// comma-separated list, or a `*/n` step, all of which can combine via commas).
+#[derive(Debug, Clone)]
+enum Field {
+    Any,
+    Values(HashSet<u32>),
+}
+
+impl Field {
+    fn matches(&self, value: u32) -> bool {
+        match self {
+            Field::Any => true,
+            Field::Values(set) => set.contains(&value),
+        }
+    }
+
+    fn parse(spec: &str, min: u32, max: u32) -> Result<Self, String> {
+        if spec == "*" {
+            return Ok(Field::Any);
+        }
+
+        let mut values = HashSet::new();
+        for part in spec.split(',') {
+            let (range_part, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| format!("bad step in cron field '{}'", spec))?),
+                None => (part, 1),
+            };
+
+            let (start, end) = if range_part == "*" {
+                (min, max)
+            } else if let Some((lo, hi)) = range_part.split_once('-') {
+                let lo = lo.parse::<u32>().map_err(|_| format!("bad range in cron field '{}'", spec))?;
+                let hi = hi.parse::<u32>().map_err(|_| format!("bad range in cron field '{}'", spec))?;
+                (lo, hi)
+            } else {
+                let v = range_part.parse::<u32>().map_err(|_| format!("bad value in cron field '{}'", spec))?;
+                (v, v)
+            };
+
+            if start < min || end > max || start > end {
+                return Err(format!("cron field '{}' out of range {}-{}", spec, min, max));
+            }
+
+            let mut v = start;
+            while v <= end {
+                values.insert(v);
+                v += step;
+            }
+        }
+
+        Ok(Field::Values(values))
+    }
+}
+
+/// A standard 5-field cron expression (`minute hour day_of_month month
+/// day_of_week`), supporting `*`, `*/n`, single values, `a-b` ranges, and
+/// comma-separated lists of any of the above. When both `day_of_month` and
+/// `day_of_week` are restricted, a day matches if *either* matches, per
+/// standard cron semantics; `next_after` is a brute-force minute-by-minute
+/// search rather than a closed-form solve, which is simple to get right and
+/// fast enough for the once-a-tick granularity schedules run at.
+#[derive(Debug, Clone)]
+pub struct CronExpr {
+    raw: String,
+    minute: Field,
+    hour: Field,
+    day_of_month: Field,
+    month: Field,
+    day_of_week: Field,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+/// How far into the future `next_after` will search before giving up; guards
+/// against spinning forever on an expression that can never match (e.g.
+/// `day_of_month` and `month` combinations that never coincide).
+const MAX_SEARCH_MINUTES: i64 = 4 * 366 * 24 * 60;
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "cron expression '{}' must have 5 fields (minute hour day_of_month month day_of_week), got {}",
+                expr,
+                fields.len()
+            ));
+        }
+
+        Ok(Self {
+            raw: expr.to_string(),
+            minute: Field::parse(fields[0], 0, 59)?,
+            hour: Field::parse(fields[1], 0, 23)?,
+            day_of_month: Field::parse(fields[2], 1, 31)?,
+            month: Field::parse(fields[3], 1, 12)?,
+            day_of_week: Field::parse(fields[4], 0, 6)?,
+            day_of_month_restricted: fields[2] != "*",
+            day_of_week_restricted: fields[4] != "*",
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    fn day_matches(&self, dt: &DateTime<Utc>) -> bool {
+        let dom_match = self.day_of_month.matches(dt.day());
+        // chrono's Weekday::num_days_from_sunday gives the standard cron 0=Sunday numbering
+        let dow_match = self.day_of_week.matches(dt.weekday().num_days_from_sunday());
+
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom_match || dow_match,
+            _ => dom_match && dow_match,
+        }
+    }
+
+    fn matches(&self, dt: &DateTime<Utc>) -> bool {
+        self.minute.matches(dt.minute())
+            && self.hour.matches(dt.hour())
+            && self.month.matches(dt.month())
+            && self.day_matches(dt)
+    }
+
+    /// The first minute-aligned instant strictly after `after` that this
+    /// expression matches, or `None` if nothing matches within
+    /// `MAX_SEARCH_MINUTES`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut candidate = (after + Duration::minutes(1))
+            .with_second(0)
+            .and_then(|d| d.with_nanosecond(0))?;
+
+        for _ in 0..MAX_SEARCH_MINUTES {
+            if self.matches(&candidate) {
+                return Some(candidate);
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse_rejects_wrong_field_count() {
+        assert!(CronExpr::parse("* * *").is_err());
+        assert!(CronExpr::parse("0 0 1 1 0 extra").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_out_of_range_values() {
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("* 24 * * *").is_err());
+        assert!(CronExpr::parse("* * 0 * *").is_err());
+        assert!(CronExpr::parse("* * * 13 *").is_err());
+        assert!(CronExpr::parse("* * * * 7").is_err());
+    }
+
+    #[test]
+    fn parse_accepts_wildcards_steps_ranges_and_lists() {
+        assert!(CronExpr::parse("*/15 * * * *").is_ok());
+        assert!(CronExpr::parse("0 9-17 * * *").is_ok());
+        assert!(CronExpr::parse("0,30 * * * *").is_ok());
+        assert!(CronExpr::parse("0 0 1,15 * *").is_ok());
+    }
+
+    #[test]
+    fn next_after_every_15_minutes() {
+        let cron = CronExpr::parse("*/15 * * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 10, 5, 0).unwrap();
+        let next = cron.next_after(after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 1, 10, 15, 0).unwrap());
+    }
+
+    #[test]
+    fn next_after_rolls_into_next_hour_and_day() {
+        let cron = CronExpr::parse("0 0 * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 23, 30, 0).unwrap();
+        let next = cron.next_after(after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn next_after_is_strictly_after_an_exact_match() {
+        // `after` itself matches the expression; next_after must still advance
+        // at least a minute rather than returning `after` unchanged.
+        let cron = CronExpr::parse("30 10 * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 10, 30, 0).unwrap();
+        let next = cron.next_after(after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 2, 10, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn day_matches_is_and_when_only_one_of_dom_dow_is_restricted() {
+        // day_of_week unrestricted ("*"): only day_of_month needs to match.
+        let cron = CronExpr::parse("0 0 15 * *").unwrap();
+        let on_the_15th = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+        let not_the_15th = Utc.with_ymd_and_hms(2024, 3, 16, 0, 0, 0).unwrap();
+        assert!(cron.day_matches(&on_the_15th));
+        assert!(!cron.day_matches(&not_the_15th));
+    }
+
+    #[test]
+    fn day_matches_is_or_when_both_dom_and_dow_are_restricted() {
+        // day_of_month is restricted to the 1st and day_of_week to Monday (1):
+        // 2024-03-11 (a Monday) matches via the day_of_week side of the OR,
+        // 2024-03-01 via the day_of_month side, and 2024-03-12 via neither.
+        let cron = CronExpr::parse("0 0 1 * 1").unwrap();
+        let matches_via_dow = Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap(); // a Monday
+        let matches_via_dom = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        let matches_neither = Utc.with_ymd_and_hms(2024, 3, 12, 0, 0, 0).unwrap(); // a Tuesday, not the 1st
+        assert!(cron.day_matches(&matches_via_dow));
+        assert!(cron.day_matches(&matches_via_dom));
+        assert!(!cron.day_matches(&matches_neither));
+    }
+
+    #[test]
+    fn next_after_none_for_an_impossible_expression() {
+        // February never has a 30th, so this can never match within the search window.
+        let cron = CronExpr::parse("0 0 30 2 *").unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(cron.next_after(after), None);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod cron;
+pub mod scheduler;
+
+pub use cron::CronExpr;
+pub use scheduler::{OverlapPolicy, ScheduleStatus, TeamScheduler};
@@ -0,0 +1,295 @@
+use crate::memory::sqlite::SqliteMemory;
+use crate::scheduler::cron::CronExpr;
+use crate::team::TeamDispatcher;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// What happens when a schedule entry comes due while its previous run is
+/// still in flight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Drop this tick; the entry's `next_run` still advances normally
+    Skip,
+    /// Start another run alongside the one still in flight
+    Allow,
+}
+
+impl OverlapPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OverlapPolicy::Skip => "skip",
+            OverlapPolicy::Allow => "allow",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "allow" => OverlapPolicy::Allow,
+            _ => OverlapPolicy::Skip,
+        }
+    }
+}
+
+struct ScheduleEntry {
+    id: String,
+    team_name: String,
+    task: String,
+    cron: CronExpr,
+    last_run: Option<DateTime<Utc>>,
+    next_run: DateTime<Utc>,
+    enabled: bool,
+    overlap: OverlapPolicy,
+}
+
+/// Point-in-time metadata about a registered schedule entry
+#[derive(Debug, Clone)]
+pub struct ScheduleStatus {
+    pub id: String,
+    pub team_name: String,
+    pub task: String,
+    pub cron_expr: String,
+    pub last_run: Option<DateTime<Utc>>,
+    pub next_run: DateTime<Utc>,
+    pub enabled: bool,
+}
+
+/// Recurring dispatch of `Team`s on a cron schedule. Where `TeamDispatcher`
+/// only ever fires once per `execute` call, `TeamScheduler` owns a set of
+/// `{ team, task, cron }` entries and a background loop (`spawn`) that fires
+/// `TeamDispatcher::execute` whenever one comes due, so a team can run as an
+/// unattended daemon job instead of only on direct request. Entries persist
+/// to `SqliteMemory` so they and their `last_run` timestamps survive a
+/// restart.
+pub struct TeamScheduler {
+    entries: Vec<ScheduleEntry>,
+    dispatchers: HashMap<String, Arc<Mutex<TeamDispatcher>>>,
+    /// Tracks which entry ids currently have a run in flight, consulted by
+    /// `OverlapPolicy::Skip`
+    running: HashMap<String, bool>,
+    store: Arc<SqliteMemory>,
+}
+
+impl TeamScheduler {
+    /// Load any previously persisted entries from `db_path` (a fresh file if
+    /// none exist yet). Teams must still be attached via `register_team`
+    /// before their entries can actually fire.
+    pub async fn new(db_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let store = Arc::new(SqliteMemory::new(db_path)?);
+        let mut entries = Vec::new();
+
+        for row in store.load_schedule_entries().await? {
+            let (id, team_name, task, cron_expr, last_run, next_run, enabled, overlap) = row;
+            let cron = CronExpr::parse(&cron_expr)?;
+            entries.push(ScheduleEntry {
+                id,
+                team_name,
+                task,
+                cron,
+                last_run: last_run.and_then(|s| DateTime::parse_from_rfc3339(&s).ok()).map(|d| d.with_timezone(&Utc)),
+                next_run: DateTime::parse_from_rfc3339(&next_run)?.with_timezone(&Utc),
+                enabled,
+                overlap: OverlapPolicy::parse(&overlap),
+            });
+        }
+        entries.sort_by_key(|e| e.next_run);
+
+        Ok(Self {
+            entries,
+            dispatchers: HashMap::new(),
+            running: HashMap::new(),
+            store,
+        })
+    }
+
+    /// Attach a live `TeamDispatcher` under `team_name` so entries referring
+    /// to it can actually fire; a persisted entry whose team was never
+    /// registered this process just sits idle instead of erroring.
+    pub fn register_team(&mut self, team_name: impl Into<String>, dispatcher: TeamDispatcher) {
+        self.dispatchers.insert(team_name.into(), Arc::new(Mutex::new(dispatcher)));
+    }
+
+    /// Register a new recurring dispatch, computing its first `next_run` from
+    /// `cron_expr` relative to now, and persisting it. Returns the generated
+    /// entry id.
+    pub async fn add_schedule(
+        &mut self,
+        team_name: impl Into<String>,
+        task: impl Into<String>,
+        cron_expr: &str,
+        overlap: OverlapPolicy,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let cron = CronExpr::parse(cron_expr)?;
+        let next_run = cron.next_after(Utc::now()).ok_or("cron expression never matches")?;
+        let id = Uuid::new_v4().to_string();
+        let team_name = team_name.into();
+        let task = task.into();
+
+        self.store
+            .save_schedule_entry(&id, &team_name, &task, cron.as_str(), None, &next_run.to_rfc3339(), true, overlap.as_str())
+            .await?;
+
+        self.entries.push(ScheduleEntry {
+            id: id.clone(),
+            team_name,
+            task,
+            cron,
+            last_run: None,
+            next_run,
+            enabled: true,
+            overlap,
+        });
+        self.entries.sort_by_key(|e| e.next_run);
+
+        Ok(id)
+    }
+
+    /// Stop an entry from firing without forgetting it
+    pub async fn disable(&mut self, id: &str) -> bool {
+        self.set_enabled(id, false).await
+    }
+
+    /// Resume an entry disabled by `disable`
+    pub async fn enable(&mut self, id: &str) -> bool {
+        self.set_enabled(id, true).await
+    }
+
+    async fn set_enabled(&mut self, id: &str, enabled: bool) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) else {
+            return false;
+        };
+        entry.enabled = enabled;
+        let (team_name, task, cron_raw, last_run, next_run, overlap) = (
+            entry.team_name.clone(),
+            entry.task.clone(),
+            entry.cron.as_str().to_string(),
+            entry.last_run.map(|d| d.to_rfc3339()),
+            entry.next_run.to_rfc3339(),
+            entry.overlap.as_str(),
+        );
+        let _ = self
+            .store
+            .save_schedule_entry(id, &team_name, &task, &cron_raw, last_run.as_deref(), &next_run, enabled, overlap)
+            .await;
+        true
+    }
+
+    /// Metadata for every registered entry, enabled or disabled
+    pub fn list_schedules(&self) -> Vec<ScheduleStatus> {
+        self.entries
+            .iter()
+            .map(|e| ScheduleStatus {
+                id: e.id.clone(),
+                team_name: e.team_name.clone(),
+                task: e.task.clone(),
+                cron_expr: e.cron.as_str().to_string(),
+                last_run: e.last_run,
+                next_run: e.next_run,
+                enabled: e.enabled,
+            })
+            .collect()
+    }
+}
+
+/// Run every due, enabled entry once: honors `OverlapPolicy::Skip` against
+/// `running`, dispatches via the entry's registered `TeamDispatcher` in its
+/// own task (so a slow team run doesn't block the next tick), then advances
+/// `next_run` from the cron expression and persists the new `last_run`/`next_run`.
+async fn tick(scheduler: &Arc<Mutex<TeamScheduler>>) {
+    let now = Utc::now();
+    let due: Vec<String> = {
+        let sched = scheduler.lock().await;
+        sched
+            .entries
+            .iter()
+            .filter(|e| e.enabled && e.next_run <= now)
+            .map(|e| e.id.clone())
+            .collect()
+    };
+
+    for id in due {
+        let (team_name, task, overlap, already_running) = {
+            let sched = scheduler.lock().await;
+            let Some(entry) = sched.entries.iter().find(|e| e.id == id) else { continue };
+            let already_running = *sched.running.get(&id).unwrap_or(&false);
+            (entry.team_name.clone(), entry.task.clone(), entry.overlap, already_running)
+        };
+
+        if overlap == OverlapPolicy::Skip && already_running {
+            tracing::warn!(schedule_id = %id, team = %team_name, "skipping tick: previous run still in flight");
+        } else {
+            let dispatcher = scheduler.lock().await.dispatchers.get(&team_name).cloned();
+            match dispatcher {
+                Some(dispatcher) => {
+                    scheduler.lock().await.running.insert(id.clone(), true);
+                    let scheduler = scheduler.clone();
+                    let id_for_task = id.clone();
+                    tokio::spawn(async move {
+                        let result = dispatcher.lock().await.execute(&task).await;
+                        if let Err(e) = &result {
+                            tracing::warn!(schedule_id = %id_for_task, error = %e, "scheduled team run failed");
+                        }
+                        scheduler.lock().await.running.insert(id_for_task, false);
+                    });
+                }
+                None => tracing::warn!(schedule_id = %id, team = %team_name, "no dispatcher registered for scheduled team"),
+            }
+        }
+
+        let mut sched = scheduler.lock().await;
+        let Some(entry) = sched.entries.iter_mut().find(|e| e.id == id) else { continue };
+        entry.last_run = Some(now);
+        entry.next_run = entry.cron.next_after(now).unwrap_or(now + ChronoDuration::days(365 * 100));
+        sched.entries.sort_by_key(|e| e.next_run);
+
+        let (team_name, task, cron_raw, last_run, next_run, enabled, overlap_str, store) = {
+            let entry = sched.entries.iter().find(|e| e.id == id).expect("just updated");
+            (
+                entry.team_name.clone(),
+                entry.task.clone(),
+                entry.cron.as_str().to_string(),
+                entry.last_run.map(|d| d.to_rfc3339()),
+                entry.next_run.to_rfc3339(),
+                entry.enabled,
+                entry.overlap.as_str(),
+                sched.store.clone(),
+            )
+        };
+        drop(sched);
+        if let Err(e) = store
+            .save_schedule_entry(&id, &team_name, &task, &cron_raw, last_run.as_deref(), &next_run, enabled, overlap_str)
+            .await
+        {
+            tracing::warn!(schedule_id = %id, error = %e, "failed to persist schedule entry");
+        }
+    }
+}
+
+/// Spawn a background task that sleeps until the soonest enabled entry's
+/// `next_run` (capped at 60s, so a newly-added entry with a closer deadline
+/// is noticed promptly) and ticks due entries; hold onto the returned handle
+/// to cancel the loop.
+pub fn spawn(scheduler: Arc<Mutex<TeamScheduler>>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let sleep_for = {
+                let sched = scheduler.lock().await;
+                let now = Utc::now();
+                sched
+                    .entries
+                    .iter()
+                    .filter(|e| e.enabled)
+                    .map(|e| e.next_run)
+                    .min()
+                    .map(|next| (next - now).to_std().unwrap_or(Duration::from_secs(0)))
+                    .unwrap_or(Duration::from_secs(60))
+            };
+
+            tokio::time::sleep(sleep_for.clamp(Duration::from_millis(200), Duration::from_secs(60))).await;
+            tick(&scheduler).await;
+        }
+    })
+}
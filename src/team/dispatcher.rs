@@ -1,63 +1,208 @@
 use crate::agent::Agent;
+use crate::error::HelixorError;
 use crate::memory::sqlite::SqliteMemory;
 use crate::model::openai::OpenAiClient;
-use crate::team::{Team, TeamWorkflow, StateTransition, DAGNode, DAGEdge};
+use crate::model::{Message, Model};
+use crate::role::RoleRegistry;
+use crate::team::{Team, TeamAgent, TeamWorkflow, StateTransition, DAGNode, DAGEdge};
 use crate::tool::load_tools;
 use crate::workflow::runner::WorkflowRunner;
-use std::collections::HashMap;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+use uuid::Uuid;
 
 /// Dispatches tasks to team members and manages workflow execution
 pub struct TeamDispatcher {
     team: Team,
-    agents: HashMap<String, Agent>,
+    agents: HashMap<String, Arc<Mutex<Agent>>>,
     runner: WorkflowRunner,
+    /// Caps how many agents run at once in `execute_parallel` and per DAG
+    /// wave, so a wide fan-out doesn't open an unbounded number of LLM
+    /// connections at the same time. Defaults to `num_cpus::get()`.
+    concurrency_limit: usize,
+    /// Backs `resume`: every FSM/DAG run checkpoints its progress here after
+    /// each state/node completes, keyed by a `run_id` generated at the start
+    /// of that run, so a crash mid-run loses at most the in-flight step.
+    run_memory: Arc<SqliteMemory>,
+}
+
+/// Where an FSM/DAG run currently stands; persisted alongside `RunRecord`
+/// after every step so `TeamDispatcher::resume` can tell a finished run from
+/// one that's still mid-flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+impl RunStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Pending => "pending",
+            RunStatus::Running => "running",
+            RunStatus::Done => "done",
+            RunStatus::Failed => "failed",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "pending" => RunStatus::Pending,
+            "done" => RunStatus::Done,
+            "failed" => RunStatus::Failed,
+            _ => RunStatus::Running,
+        }
+    }
+}
+
+/// Which executor a persisted run belongs to, so `resume` knows how to
+/// reinterpret its `current`/`outputs` fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunKind {
+    Fsm,
+    Dag,
+}
+
+impl RunKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RunKind::Fsm => "fsm",
+            RunKind::Dag => "dag",
+        }
+    }
+}
+
+/// A run's progress as loaded back out of `SqliteMemory::load_run`.
+/// For FSM runs, `current` is the FSM state name and `outputs` is keyed by
+/// step index ("1", "2", ...). For DAG runs, `current` is unused and
+/// `outputs` is keyed by `DAGNode::id`.
+struct RunRecord {
+    kind: RunKind,
+    current: String,
+    status: RunStatus,
+    outputs: HashMap<String, String>,
+}
+
+/// Persist one step of progress for `run_id`. Failures to persist are logged
+/// but don't fail the run itself — durability is a best-effort safety net,
+/// not a correctness requirement of the workflow executors.
+async fn checkpoint_run(
+    run_memory: &SqliteMemory,
+    run_id: &str,
+    kind: RunKind,
+    current: &str,
+    status: RunStatus,
+    outputs: &HashMap<String, String>,
+) {
+    let outputs_json = serde_json::to_string(outputs).unwrap_or_default();
+    if let Err(e) = run_memory
+        .save_run(run_id, kind.as_str(), current, status.as_str(), &outputs_json)
+        .await
+    {
+        tracing::warn!(run_id, error = %e, "failed to checkpoint workflow run");
+    }
+}
+
+async fn load_run(run_memory: &SqliteMemory, run_id: &str) -> Result<RunRecord, HelixorError> {
+    let (kind, current, status, outputs_json) = run_memory
+        .load_run(run_id)
+        .await?
+        .ok_or_else(|| HelixorError::Other(format!("no persisted run found for run_id '{}'", run_id)))?;
+
+    let kind = match kind.as_str() {
+        "fsm" => RunKind::Fsm,
+        "dag" => RunKind::Dag,
+        other => return Err(HelixorError::Other(format!("unknown persisted workflow_kind '{}'", other))),
+    };
+    let outputs: HashMap<String, String> = serde_json::from_str(&outputs_json).unwrap_or_default();
+
+    Ok(RunRecord {
+        kind,
+        current,
+        status: RunStatus::parse(&status),
+        outputs,
+    })
+}
+
+/// Build the in-process `Agent` for one `TeamAgent`, swapping in its `Team`
+/// persona role if one matches. Shared by `TeamDispatcher::new` and pull-mode
+/// worker deployment (`deploy::deploy_team_instance`) so both construct team
+/// members identically.
+pub(crate) fn build_team_agent(team: &Team, team_agent: &TeamAgent) -> Result<Agent, HelixorError> {
+    let api_key = std::env::var("OPENAI_API_KEY").map_err(|_| HelixorError::MissingApiKey)?;
+
+    let model = Box::new(OpenAiClient::new(api_key));
+
+    // A `role` naming an entry in the team's `[roles]` table swaps in that
+    // persona's system prompt (and tool set, if it restricts one); an
+    // unrecognized or blank role falls back to the agent's own free-text
+    // `instructions`/`tools`, so existing team.toml files keep working.
+    let persona = RoleRegistry::from_map(team.roles.clone()).get(&team_agent.role).cloned();
+    let persona = persona.as_ref();
+    let instructions = persona
+        .map(|r| r.system_prompt.clone())
+        .unwrap_or_else(|| team_agent.instructions.clone());
+    let tools = load_tools(persona.and_then(|r| r.tools.as_ref()).unwrap_or(&team_agent.tools));
+
+    // Use shared memory if specified, otherwise individual memory
+    let memory_path = team.shared_memory.clone()
+        .unwrap_or_else(|| format!("memory_{}.db", team_agent.name));
+    let memory = Arc::new(SqliteMemory::new(&memory_path)?);
+
+    Ok(Agent::new(
+        team_agent.name.clone(),
+        instructions,
+        model,
+        tools,
+        memory,
+    ))
 }
 
 impl TeamDispatcher {
-    pub async fn new(team: Team) -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn new(team: Team) -> Result<Self, HelixorError> {
         let mut agents = HashMap::new();
-        
+
         // Initialize each agent in the team
         for team_agent in &team.agents {
-            let api_key = std::env::var("OPENAI_API_KEY")
-                .expect("❌ Missing OPENAI_API_KEY in environment");
-            
-            let model = Box::new(OpenAiClient::new(api_key));
-            let tools = load_tools(&team_agent.tools);
-            
-            // Use shared memory if specified, otherwise individual memory
-            let memory_path = team.shared_memory.clone()
-                .unwrap_or_else(|| format!("memory_{}.db", team_agent.name));
-            let memory = Arc::new(SqliteMemory::new(&memory_path)?);
-            
-            let agent = Agent::new(
-                team_agent.name.clone(),
-                team_agent.instructions.clone(),
-                model,
-                tools,
-                memory,
-            );
-            
-            agents.insert(team_agent.name.clone(), agent);
+            let agent = build_team_agent(&team, team_agent)?;
+            agents.insert(team_agent.name.clone(), Arc::new(Mutex::new(agent)));
         }
-        
+
         let runner = WorkflowRunner::new();
-        
+
+        // A dedicated db, separate from any agent's own memory, so resumable
+        // run state survives independently of which agents a team happens to
+        // use (and doesn't collide with their conversational memory tables).
+        let run_memory = Arc::new(SqliteMemory::new(&format!("runs_{}.db", team.name))?);
+
         Ok(Self {
             team,
             agents,
             runner,
+            concurrency_limit: num_cpus::get(),
+            run_memory,
         })
     }
-    
+
+    /// Override the default (`num_cpus::get()`) cap on agents running at
+    /// once in `execute_parallel` and per DAG wave
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = limit.max(1);
+        self
+    }
+
     /// Execute a task using the team's workflow
-    pub async fn execute(&mut self, task: &str) -> Result<String, Box<dyn std::error::Error>> {
+    pub async fn execute(&mut self, task: &str) -> Result<String, HelixorError> {
         println!("🤝 Team '{}' executing task: {}", self.team.name, task);
-        
+
         // Clone the workflow to avoid borrowing issues
         let workflow = self.team.workflow.clone();
-        
+
         match workflow {
             TeamWorkflow::RoundRobin => {
                 self.execute_round_robin(task).await
@@ -68,307 +213,548 @@ impl TeamDispatcher {
             TeamWorkflow::Parallel => {
                 self.execute_parallel(task).await
             }
-            TeamWorkflow::FSM { states, transitions, initial_state } => {
+            TeamWorkflow::FSM { states, transitions, initial_state, agent_map } => {
                 // Use FSM config if available, otherwise use the workflow's built-in config
+                let run_id = Uuid::new_v4().to_string();
+                // FSM runs checkpoint to run_memory as they go, so a crash
+                // mid-run is recoverable via `resume(run_id, ...)` - but only
+                // if the operator knows this id, since nothing else surfaces
+                // it. Print and log it up front, before any step can fail.
+                println!("🆔 Run id (for `resume` if this is interrupted): {}", run_id);
+                tracing::info!(run_id = %run_id, "starting resumable FSM run");
                 if let Some(fsm_config) = &self.team.fsm {
                     let states = fsm_config.states.clone();
                     let transitions = fsm_config.transitions.clone();
                     let initial_state = fsm_config.initial_state.clone();
-                    self.execute_fsm(task, &states, &transitions, &initial_state).await
+                    let max_steps = fsm_config.max_steps;
+                    let agent_map = fsm_config.agent_map.clone();
+                    self.execute_fsm(&run_id, task, &states, &transitions, &initial_state, max_steps, &agent_map, HashMap::new()).await
                 } else {
-                    self.execute_fsm(task, &states, &transitions, &initial_state).await
+                    self.execute_fsm(&run_id, task, &states, &transitions, &initial_state, None, &agent_map, HashMap::new()).await
                 }
             }
             TeamWorkflow::DAG { nodes, edges } => {
                 // Use DAG config if available, otherwise use the workflow's built-in config
+                let run_id = Uuid::new_v4().to_string();
+                // Same rationale as the FSM branch above: surface the id an
+                // interrupted run would need for `resume`.
+                println!("🆔 Run id (for `resume` if this is interrupted): {}", run_id);
+                tracing::info!(run_id = %run_id, "starting resumable DAG run");
                 if let Some(dag_config) = &self.team.dag {
                     let nodes = dag_config.nodes.clone();
                     let edges = dag_config.edges.clone();
-                    self.execute_dag(task, &nodes, &edges).await
+                    self.execute_dag(&run_id, task, &nodes, &edges, HashMap::new()).await
                 } else {
-                    self.execute_dag(task, &nodes, &edges).await
+                    self.execute_dag(&run_id, task, &nodes, &edges, HashMap::new()).await
                 }
             }
         }
     }
-    
+
+    /// Resume a previously interrupted FSM or DAG run from its last
+    /// checkpoint in `run_memory`. Re-runs against the team's *current*
+    /// `fsm`/`dag` config (an already-`Done`/`Failed` run simply replays its
+    /// persisted result instead of re-executing any steps).
+    pub async fn resume(&mut self, run_id: &str, task: &str) -> Result<String, HelixorError> {
+        let record = load_run(&self.run_memory, run_id).await?;
+
+        if record.status == RunStatus::Done || record.status == RunStatus::Failed {
+            let mut ordered: Vec<(String, String)> = record.outputs.into_iter().collect();
+            ordered.sort_by(|a, b| a.0.cmp(&b.0));
+            return Ok(ordered.into_iter().map(|(_, v)| v).collect::<Vec<_>>().join("\n\n"));
+        }
+
+        match record.kind {
+            RunKind::Fsm => {
+                let fsm_config = self
+                    .team
+                    .fsm
+                    .clone()
+                    .ok_or_else(|| HelixorError::Other("team has no FSM config to resume against".to_string()))?;
+                self.execute_fsm(
+                    run_id,
+                    task,
+                    &fsm_config.states,
+                    &fsm_config.transitions,
+                    &record.current,
+                    fsm_config.max_steps,
+                    &fsm_config.agent_map,
+                    record.outputs,
+                )
+                .await
+            }
+            RunKind::Dag => {
+                let dag_config = self
+                    .team
+                    .dag
+                    .clone()
+                    .ok_or_else(|| HelixorError::Other("team has no DAG config to resume against".to_string()))?;
+                self.execute_dag(run_id, task, &dag_config.nodes, &dag_config.edges, record.outputs).await
+            }
+        }
+    }
+
     /// Round-robin: each agent gets a turn
-    async fn execute_round_robin(&mut self, task: &str) -> Result<String, Box<dyn std::error::Error>> {
+    async fn execute_round_robin(&mut self, task: &str) -> Result<String, HelixorError> {
         let mut results = Vec::new();
-        
-        for (agent_name, agent) in &mut self.agents {
+
+        for (agent_name, agent) in &self.agents {
             println!("🔄 {} taking turn...", agent_name);
-            
+
             // Run the agent with the task directly
-            let output = run_agent_silently(agent, task).await;
+            let (output, _steps) = run_agent_silently(&mut *agent.lock().await, task).await;
             results.push(format!("{}: {}", agent_name, output));
         }
-        
+
         Ok(results.join("\n\n"))
     }
-    
+
     /// Chain-of-Thought: agents pass results to next agent
-    async fn execute_chain_of_thought(&mut self, task: &str) -> Result<String, Box<dyn std::error::Error>> {
+    async fn execute_chain_of_thought(&mut self, task: &str) -> Result<String, HelixorError> {
         let mut current_input = task.to_string();
         let mut results = Vec::new();
-        
+
         let agent_names: Vec<String> = self.agents.keys().cloned().collect();
-        
+
         for (i, agent_name) in agent_names.iter().enumerate() {
             println!("🔗 {} in chain (step {})...", agent_name, i + 1);
-            
+
             // Get the actual agent and run it
-            if let Some(agent) = self.agents.get_mut(agent_name) {
+            if let Some(agent) = self.agents.get(agent_name) {
                 // Run the agent with the current input directly
-                let output = run_agent_silently(agent, &current_input).await;
+                let (output, _steps) = run_agent_silently(&mut *agent.lock().await, &current_input).await;
                 results.push(format!("Step {} ({}): {}", i + 1, agent_name, output));
                 current_input = output; // Pass output to next agent
             } else {
                 results.push(format!("Step {} ({}): Agent not found", i + 1, agent_name));
             }
         }
-        
+
         Ok(results.join("\n\n"))
     }
-    
-    /// Parallel: all agents work simultaneously on the same task
-    async fn execute_parallel(&mut self, task: &str) -> Result<String, Box<dyn std::error::Error>> {
+
+    /// Parallel: all agents work simultaneously on the same task. Each agent
+    /// runs in its own `tokio::task`, bounded by `concurrency_limit` via a
+    /// shared `Semaphore` so a team with many members doesn't open an agent
+    /// connection per member all at once.
+    async fn execute_parallel(&mut self, task: &str) -> Result<String, HelixorError> {
         println!("⚡ Executing parallel workflow...");
-        
-        // For now, we'll run agents sequentially to avoid borrowing issues
-        // In a real implementation, you'd want to use Arc<Mutex<Agent>> or similar
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency_limit));
+        let mut futures = FuturesUnordered::new();
+
+        for (agent_name, agent) in &self.agents {
+            let agent_name = agent_name.clone();
+            let agent = agent.clone();
+            let semaphore = semaphore.clone();
+            let task = task.to_string();
+
+            futures.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.expect("semaphore never closed");
+                println!("⚡ {} working in parallel...", agent_name);
+                let (output, _steps) = run_agent_silently(&mut *agent.lock().await, &task).await;
+                (agent_name, output)
+            }));
+        }
+
         let mut results = Vec::new();
-        
-        for (agent_name, agent) in &mut self.agents {
-            println!("⚡ {} working in parallel...", agent_name);
-            
-            // Run the agent with the task
-            let output = run_agent_silently(agent, task).await;
+        while let Some(joined) = futures.next().await {
+            let (agent_name, output) = joined?;
             results.push(format!("{}: {}", agent_name, output));
         }
-        
+
         Ok(results.join("\n\n"))
     }
-    
-    /// FSM: Finite State Machine workflow
+
+    /// FSM: drives agents through `states` according to `transitions`,
+    /// stopping at a terminal state (one with no outgoing transition whose
+    /// condition matches). Returns `HelixorError::WorkflowStuck` if `max_steps`
+    /// ticks run out without reaching one, or if the current state isn't in
+    /// `states`; a state with no entry in `agent_map` is `HelixorError::Other`
+    /// and one mapped to an agent the team doesn't have is `AgentNotFound`,
+    /// rather than burying any of these in the joined output.
+    /// This is what lets a team express a review/revision loop like
+    /// Writer → Reviewer → Writer instead of only a fixed chain. Checkpoints
+    /// to `run_memory` after every completed step under `run_id`, so a crash
+    /// mid-run can be continued via `resume` instead of restarted; `completed_outputs`
+    /// (step index -> formatted line, empty for a fresh run) seeds that replay.
     async fn execute_fsm(
         &mut self,
+        run_id: &str,
         task: &str,
         states: &[String],
         transitions: &[StateTransition],
         initial_state: &str,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+        max_steps: Option<usize>,
+        agent_map: &HashMap<String, String>,
+        completed_outputs: HashMap<String, String>,
+    ) -> Result<String, HelixorError> {
         let mut current_state = initial_state.to_string();
-        let mut results = Vec::new();
-        let mut visited_states = std::collections::HashSet::new();
-        
-        // Prevent infinite loops
-        let max_iterations = states.len() * 2;
-        let mut iteration = 0;
-        
-        while let Some(state) = states.iter().find(|s| **s == current_state) {
-            iteration += 1;
-            if iteration > max_iterations {
-                results.push("⚠️ FSM: Maximum iterations reached, stopping to prevent infinite loop".to_string());
-                break;
-            }
-            
-            if visited_states.contains(&current_state) {
-                results.push(format!("⚠️ FSM: State '{}' already visited, stopping loop", current_state));
-                break;
+        let mut outputs = completed_outputs;
+        let mut results: Vec<String> = {
+            let mut ordered: Vec<(usize, String)> = outputs
+                .iter()
+                .filter_map(|(k, v)| k.parse::<usize>().ok().map(|n| (n, v.clone())))
+                .collect();
+            ordered.sort_by_key(|(n, _)| *n);
+            ordered.into_iter().map(|(_, v)| v).collect()
+        };
+        let already_run = results.len();
+        let max_steps = max_steps.unwrap_or_else(|| states.len() * 2).max(1);
+        let mut transition_cache = TransitionCache::new();
+
+        checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Running, &outputs).await;
+
+        for step in (already_run + 1)..=max_steps {
+            if !states.iter().any(|s| *s == current_state) {
+                checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Failed, &outputs).await;
+                return Err(HelixorError::WorkflowStuck { state: current_state });
             }
-            
-            visited_states.insert(current_state.clone());
-            println!("🏭 FSM State: {} (iteration {})", state, iteration);
-            
-            // Find agent responsible for this state
-            if let Some((agent_name, agent)) = self.agents.iter_mut().find(|(name, _)| {
-                name.as_str().contains(state) || state.contains(name.as_str())
-            }) {
-                // Run the agent for this state
-                let state_task = format!("State: {}. Task: {}", state, task);
-                let output = run_agent_silently(agent, &state_task).await;
-                results.push(format!("State {} ({}): {}", state, agent_name, output));
-                
-                // Determine next state based on transitions and agent output
-                let next_state = self.determine_next_state(&current_state, transitions, &output).await;
-                
-                if let Some(next) = next_state {
-                    current_state = next;
-                } else {
+
+            println!("🏭 FSM State: {} (step {})", current_state, step);
+
+            // Find the agent responsible for this state via the FSM's explicit
+            // state->agent mapping, rather than guessing from name overlap.
+            let Some(agent_name) = agent_map.get(&current_state) else {
+                checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Failed, &outputs).await;
+                return Err(HelixorError::Other(format!(
+                    "FSM state '{}' has no agent assigned in agent_map",
+                    current_state
+                )));
+            };
+            let Some(agent) = self.agents.get(agent_name) else {
+                checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Failed, &outputs).await;
+                return Err(HelixorError::AgentNotFound(agent_name.clone()));
+            };
+            let agent_name = agent_name.clone();
+
+            let state_task = format!("State: {}. Task: {}", current_state, task);
+            let (output, steps) = run_agent_silently(&mut *agent.lock().await, &state_task).await;
+            println!("🏭 FSM State {} took {} model step(s)", current_state, steps);
+            let line = format!("State {} ({}): {}", current_state, agent_name, output);
+            results.push(line.clone());
+            outputs.insert(step.to_string(), line);
+            checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Running, &outputs).await;
+
+            let next_state = {
+                let agent = agent.lock().await;
+                determine_next_state(&current_state, transitions, &output, &mut transition_cache, agent.model.as_ref()).await
+            };
+            match next_state {
+                Some(next) => current_state = next,
+                None => {
                     results.push(format!("✅ FSM: No more transitions from state '{}', workflow complete", current_state));
-                    break;
-                }
-            } else {
-                results.push(format!("❌ FSM: No agent found for state: {}", state));
-                break;
-            }
-        }
-        
-        Ok(results.join("\n\n"))
-    }
-    
-    /// Determine the next state based on transitions and agent output
-    async fn determine_next_state(
-        &self,
-        current_state: &str,
-        transitions: &[StateTransition],
-        agent_output: &str,
-    ) -> Option<String> {
-        // Find all possible transitions from current state
-        let possible_transitions: Vec<_> = transitions
-            .iter()
-            .filter(|t| t.from == current_state)
-            .collect();
-        
-        if possible_transitions.is_empty() {
-            return None; // No transitions available
-        }
-        
-        // For now, use simple logic based on agent output
-        // In a real implementation, you might use LLM to determine the condition
-        for transition in &possible_transitions {
-            match transition.condition.as_str() {
-                "issue_received" | "analysis_complete" | "resolution_attempted" => {
-                    // These are automatic transitions
-                    return Some(transition.to.clone());
-                }
-                "customer_satisfied" => {
-                    // Check if output suggests satisfaction
-                    if agent_output.to_lowercase().contains("satisfied") 
-                        || agent_output.to_lowercase().contains("resolved")
-                        || agent_output.to_lowercase().contains("happy") {
-                        return Some(transition.to.clone());
-                    }
-                }
-                "customer_unsatisfied" => {
-                    // Check if output suggests dissatisfaction
-                    if agent_output.to_lowercase().contains("unsatisfied")
-                        || agent_output.to_lowercase().contains("not resolved")
-                        || agent_output.to_lowercase().contains("still has issue") {
-                        return Some(transition.to.clone());
-                    }
-                }
-                _ => {
-                    // Default: take the first transition
-                    return Some(transition.to.clone());
+                    checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Done, &outputs).await;
+                    return Ok(results.join("\n\n"));
                 }
             }
         }
-        
-        // If no specific condition matched, take the first transition
-        possible_transitions.first().map(|t| t.to.clone())
+
+        checkpoint_run(&self.run_memory, run_id, RunKind::Fsm, &current_state, RunStatus::Failed, &outputs).await;
+        Err(HelixorError::WorkflowStuck { state: current_state })
     }
-    
-    /// DAG: Directed Acyclic Graph workflow
+
+    /// DAG: Kahn's-algorithm scheduling over `DAGNode`s/`DAGEdge`s. Every node
+    /// whose in-degree reaches 0 is collected into the current wave and runs
+    /// concurrently in its own `tokio::task` (bounded by `concurrency_limit`
+    /// via a shared `Semaphore`, since an unbounded wave could otherwise fan
+    /// out to every node in a wide graph at once); an edge with a `condition`
+    /// only counts toward its target's readiness if the condition is a
+    /// substring of the predecessor's output, so conditional branches that
+    /// aren't taken simply leave that path unscheduled. The next wave only
+    /// starts once the whole current wave has resolved. Checkpoints to
+    /// `run_memory` after every wave under `run_id`; `completed_nodes` (node
+    /// id -> output, empty for a fresh run) seeds already-finished nodes so a
+    /// resumed run only re-schedules what's left. Returns
+    /// `HelixorError::AgentNotFound` as soon as any node references an agent
+    /// the team doesn't have, instead of recording it as a line in the output
+    /// and leaving its dependents silently unscheduled.
     async fn execute_dag(
         &mut self,
+        run_id: &str,
         task: &str,
         nodes: &[DAGNode],
         edges: &[DAGEdge],
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        let mut results = Vec::new();
-        let mut completed_nodes = std::collections::HashSet::new();
-        let mut node_results = std::collections::HashMap::new();
-        
-        // Find starting nodes (nodes with no incoming edges)
-        let mut ready_nodes: Vec<_> = nodes.iter()
-            .filter(|node| {
-                !edges.iter().any(|edge| edge.to == node.id)
-            })
+        completed_nodes: HashMap<String, String>,
+    ) -> Result<String, HelixorError> {
+        let node_by_id: HashMap<&str, &DAGNode> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        // A `from`/`to` naming a node id that isn't in `nodes` would otherwise
+        // surface much later, as a panic on the first wave that tries to
+        // schedule the dangling id - catch it here instead, up front.
+        for edge in edges {
+            if !node_by_id.contains_key(edge.from.as_str()) {
+                return Err(HelixorError::Other(format!(
+                    "DAG edge references unknown node '{}' in its 'from' field",
+                    edge.from
+                )));
+            }
+            if !node_by_id.contains_key(edge.to.as_str()) {
+                return Err(HelixorError::Other(format!(
+                    "DAG edge references unknown node '{}' in its 'to' field",
+                    edge.to
+                )));
+            }
+        }
+
+        let mut in_degree: HashMap<&str, usize> = nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+        let mut dependents: HashMap<&str, Vec<&DAGEdge>> = HashMap::new();
+        for edge in edges {
+            *in_degree.entry(edge.to.as_str()).or_insert(0) += 1;
+            dependents.entry(edge.from.as_str()).or_default().push(edge);
+        }
+
+        let mut node_results: HashMap<String, String> = completed_nodes;
+        let mut scheduled: HashSet<String> = node_results.keys().cloned().collect();
+
+        // Replay the readiness effect of nodes that already completed before
+        // this run was (re)started, so a resumed run doesn't re-schedule them.
+        for (from_id, output) in &node_results {
+            let Some(outgoing) = dependents.get(from_id.as_str()) else { continue };
+            for edge in outgoing {
+                let condition_met = edge
+                    .condition
+                    .as_deref()
+                    .map(|c| output.to_lowercase().contains(&c.to_lowercase()))
+                    .unwrap_or(true);
+                if condition_met {
+                    if let Some(degree) = in_degree.get_mut(edge.to.as_str()) {
+                        *degree = degree.saturating_sub(1);
+                    }
+                }
+            }
+        }
+
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|(id, &d)| d == 0 && !scheduled.contains(*id))
+            .map(|(id, _)| *id)
             .collect();
-        
-        println!("📊 DAG: Starting with {} ready nodes", ready_nodes.len());
-        
-        while !ready_nodes.is_empty() {
-            let node = ready_nodes.remove(0);
-            println!("📊 DAG Node: {} (Agent: {}) - {}", node.id, node.agent, node.task);
-            
-            // Build context before mutable borrow
-            let context = self.build_dag_context(&node.id, edges, &node_results).await;
-            
-            // Find the agent for this node
-            if let Some((agent_name, agent)) = self.agents.iter_mut().find(|(name, _)| {
-                **name == node.agent
-            }) {
-                // Prepare task with context from dependencies
+
+        let mut results: Vec<String> = {
+            let mut lines: Vec<(&str, String)> = node_results
+                .iter()
+                .map(|(id, output)| {
+                    let agent_name = node_by_id.get(id.as_str()).map(|n| n.agent.as_str()).unwrap_or("unknown");
+                    (id.as_str(), format!("Node {} ({}): {}", id, agent_name, output))
+                })
+                .collect();
+            lines.sort_by_key(|(id, _)| *id);
+            lines.into_iter().map(|(_, line)| line).collect()
+        };
+
+        println!("📊 DAG: {} ready nodes, {} already complete", ready.len(), scheduled.len());
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency_limit));
+
+        while !ready.is_empty() {
+            let wave = std::mem::take(&mut ready);
+
+            let mut wave_futures = FuturesUnordered::new();
+            for node_id in wave {
+                let Some(&node) = node_by_id.get(node_id) else {
+                    checkpoint_run(&self.run_memory, run_id, RunKind::Dag, "", RunStatus::Failed, &node_results).await;
+                    return Err(HelixorError::Other(format!("DAG scheduled unknown node '{}'", node_id)));
+                };
+                let context = dag_context(node_id, edges, &node_results);
                 let node_task = format!("Task: {}. Context: {}. Original: {}", node.task, context, task);
-                
-                // Run the agent
-                let output = run_agent_silently(agent, &node_task).await;
-                let result = format!("Node {} ({}): {}", node.id, agent_name, output);
-                results.push(result.clone());
-                
-                // Store result for dependent nodes
-                node_results.insert(node.id.clone(), output);
-                completed_nodes.insert(&node.id);
-                
-                println!("✅ DAG: Completed node {}", node.id);
-            } else {
-                results.push(format!("❌ DAG: Agent '{}' not found for node {}", node.agent, node.id));
-                completed_nodes.insert(&node.id);
+
+                let node_id = node_id.to_string();
+                let agent_name = node.agent.clone();
+                let agent = self.agents.get(&agent_name).cloned();
+                let semaphore = semaphore.clone();
+
+                wave_futures.push(tokio::spawn(async move {
+                    let outcome = match agent {
+                        Some(agent) => {
+                            let _permit = semaphore.acquire_owned().await.expect("semaphore never closed");
+                            Ok(run_agent_silently(&mut *agent.lock().await, &node_task).await)
+                        }
+                        None => Err(agent_name.clone()),
+                    };
+                    (node_id, agent_name, outcome)
+                }));
             }
-            
-            // Find nodes that can now be executed (all dependencies completed)
-            for edge in edges {
-                if edge.from == node.id {
-                    let target_node = nodes.iter().find(|n| n.id == edge.to).unwrap();
-                    
-                    // Check if all dependencies of target_node are completed
-                    let all_deps_completed = edges.iter()
-                        .filter(|e| e.to == target_node.id)
-                        .all(|e| completed_nodes.contains(&e.from));
-                    
-                    if all_deps_completed && !ready_nodes.contains(&target_node) {
-                        ready_nodes.push(target_node);
-                        println!("📊 DAG: Node {} is now ready (dependencies: {:?})", 
-                                target_node.id, 
-                                edges.iter().filter(|e| e.to == target_node.id).map(|e| &e.from).collect::<Vec<_>>());
+
+            while let Some(joined) = wave_futures.next().await {
+                let (node_id, agent_name, outcome) = joined?;
+
+                let output = match outcome {
+                    Ok((output, steps)) => {
+                        println!("📊 DAG node {} took {} model step(s)", node_id, steps);
+                        output
+                    }
+                    Err(missing_agent) => {
+                        checkpoint_run(&self.run_memory, run_id, RunKind::Dag, "", RunStatus::Failed, &node_results).await;
+                        return Err(HelixorError::AgentNotFound(missing_agent));
+                    }
+                };
+                scheduled.insert(node_id.clone());
+                results.push(format!("Node {} ({}): {}", node_id, agent_name, output));
+                println!("✅ DAG: Completed node {}", node_id);
+
+                if let Some(outgoing) = dependents.get(node_id.as_str()) {
+                    for edge in outgoing {
+                        let condition_met = edge
+                            .condition
+                            .as_deref()
+                            .map(|c| output.to_lowercase().contains(&c.to_lowercase()))
+                            .unwrap_or(true);
+                        if !condition_met {
+                            continue;
+                        }
+
+                        let degree = in_degree.get_mut(edge.to.as_str()).unwrap();
+                        *degree -= 1;
+                        if *degree == 0 {
+                            ready.push(edge.to.as_str());
+                        }
                     }
                 }
+
+                node_results.insert(node_id, output);
             }
+
+            checkpoint_run(&self.run_memory, run_id, RunKind::Dag, "", RunStatus::Running, &node_results).await;
         }
-        
-        // Check if all nodes were completed
-        if completed_nodes.len() < nodes.len() {
-            let uncompleted: Vec<_> = nodes.iter()
-                .filter(|n| !completed_nodes.contains(&n.id))
-                .map(|n| &n.id)
-                .collect();
-            results.push(format!("⚠️ DAG: Some nodes could not be completed: {:?}", uncompleted));
+
+        if scheduled.len() < nodes.len() {
+            let unscheduled: Vec<_> = nodes.iter().map(|n| n.id.as_str()).filter(|id| !scheduled.contains(*id)).collect();
+            checkpoint_run(&self.run_memory, run_id, RunKind::Dag, "", RunStatus::Failed, &node_results).await;
+            return Err(HelixorError::Other(format!(
+                "DAG workflow could not schedule every node (cycle or unmet condition): {:?} never ran",
+                unscheduled
+            )));
         }
-        
+
+        checkpoint_run(&self.run_memory, run_id, RunKind::Dag, "", RunStatus::Done, &node_results).await;
         Ok(results.join("\n\n"))
     }
-    
-    /// Build context for a DAG node based on its dependencies
-    async fn build_dag_context(
-        &self,
-        node_id: &str,
-        edges: &[DAGEdge],
-        node_results: &std::collections::HashMap<String, String>,
-    ) -> String {
-        let dependencies: Vec<_> = edges.iter()
-            .filter(|e| e.to == node_id)
-            .map(|e| &e.from)
-            .collect();
-        
-        if dependencies.is_empty() {
-            return "No dependencies".to_string();
-        }
-        
-        let mut context_parts = Vec::new();
-        for dep in dependencies {
-            if let Some(result) = node_results.get(dep) {
-                context_parts.push(format!("{}: {}", dep, result));
-            }
-        }
-        
-        context_parts.join(" | ")
+}
+
+/// Caches LLM transition-condition classifications within a single FSM run, keyed
+/// by the agent output classified and the exact candidate set it was classified
+/// against, so a state that produces the same output twice (e.g. a retry loop)
+/// doesn't re-trigger a model call for something already resolved this run.
+type TransitionCache = HashMap<(String, Vec<String>), String>;
+
+/// The transition out of `current_state` whose condition matches `agent_output`,
+/// or `None` if none do (i.e. `current_state` is terminal). Conditions in
+/// `condition_matches`' built-in vocabulary are checked first, in transition
+/// order, same as before; if none of those match, the remaining conditions are
+/// treated as free-text labels and resolved via `classify_transition` instead of
+/// defaulting to "first transition wins".
+async fn determine_next_state(
+    current_state: &str,
+    transitions: &[StateTransition],
+    agent_output: &str,
+    cache: &mut TransitionCache,
+    model: &(dyn Model + Send + Sync),
+) -> Option<String> {
+    let candidates: Vec<&StateTransition> = transitions.iter().filter(|t| t.from == current_state).collect();
+
+    if let Some(t) = candidates.iter().find(|t| condition_matches(&t.condition, agent_output) == Some(true)) {
+        return Some(t.to.clone());
     }
+
+    let labels: Vec<&StateTransition> = candidates
+        .iter()
+        .filter(|t| condition_matches(&t.condition, agent_output).is_none())
+        .cloned()
+        .collect();
+
+    if labels.is_empty() {
+        return None;
+    }
+
+    let label_set: Vec<String> = labels.iter().map(|t| t.condition.clone()).collect();
+    let chosen = classify_transition(agent_output, &label_set, cache, model).await;
+
+    match chosen {
+        Some(label) => labels.iter().find(|t| t.condition == label).map(|t| t.to.clone()),
+        // Model error or an answer outside the candidate set: fall back to the
+        // pre-existing "first transition wins" behavior.
+        None => labels.first().map(|t| t.to.clone()),
+    }
+}
+
+/// Ask `model` - the state's own agent's configured model, so this respects
+/// whatever provider/endpoint the team set up rather than assuming OpenAI -
+/// which of `candidates` (transition condition labels) best matches
+/// `agent_output`, for labels outside `condition_matches`' built-in
+/// vocabulary. Returns `None` (letting the caller fall back) if the call
+/// fails or the model's answer isn't one of `candidates`. Results are cached
+/// in `cache` for the rest of the run.
+async fn classify_transition(
+    agent_output: &str,
+    candidates: &[String],
+    cache: &mut TransitionCache,
+    model: &(dyn Model + Send + Sync),
+) -> Option<String> {
+    let cache_key = (agent_output.to_string(), candidates.to_vec());
+    if let Some(cached) = cache.get(&cache_key) {
+        return Some(cached.clone());
+    }
+
+    let prompt = format!(
+        "An agent just produced the following output as part of a workflow:\n\n{}\n\n\
+         Which of these labels best describes the outcome? Respond with exactly one \
+         label from this list, verbatim, and nothing else: {}",
+        agent_output,
+        candidates.join(", ")
+    );
+
+    let reply = model
+        .generate(vec![Message { role: "user".to_string(), content: prompt }])
+        .await;
+    let answer = reply.trim();
+
+    let chosen = candidates.iter().find(|c| c.eq_ignore_ascii_case(answer))?.clone();
+    cache.insert(cache_key, chosen.clone());
+    Some(chosen)
+}
+
+/// Evaluates an FSM transition's condition against the output of the agent
+/// that just ran. Recognized vocabulary: `task_complete`, `error`,
+/// `needs_revision`, and `contains:<substr>` for an arbitrary substring
+/// check. Returns `None` for any other condition string, meaning it's a
+/// free-text label `determine_next_state` resolves via `classify_transition`
+/// instead.
+fn condition_matches(condition: &str, agent_output: &str) -> Option<bool> {
+    let output = agent_output.to_lowercase();
+
+    if let Some(substr) = condition.strip_prefix("contains:") {
+        return Some(output.contains(&substr.to_lowercase()));
+    }
+
+    match condition {
+        "task_complete" => Some(output.contains("complete") || output.contains("done")),
+        "error" => Some(output.contains("error") || output.contains("fail")),
+        "needs_revision" => Some(output.contains("revision") || output.contains("revise")),
+        _ => None,
+    }
+}
+
+/// Concatenate the outputs of a node's completed predecessors into the
+/// context string its task prompt is assembled from
+fn dag_context(node_id: &str, edges: &[DAGEdge], node_results: &HashMap<String, String>) -> String {
+    let dependencies: Vec<_> = edges.iter().filter(|e| e.to == node_id).map(|e| &e.from).collect();
+
+    if dependencies.is_empty() {
+        return "No dependencies".to_string();
+    }
+
+    dependencies
+        .into_iter()
+        .filter_map(|dep| node_results.get(dep).map(|result| format!("{}: {}", dep, result)))
+        .collect::<Vec<_>>()
+        .join(" | ")
 }
 
-/// Run an agent and capture its output using real LLM/tool execution
-async fn run_agent_silently(agent: &mut Agent, input: &str) -> String {
-    agent.run_once(input).await
+/// Run an agent and capture its output using real LLM/tool execution, along with
+/// how many model round-trips (`Agent::run_once_with_steps`) it took - the FSM and
+/// DAG executors log this so a slow step is traceable to "the model kept calling
+/// tools" rather than looking like a hang.
+async fn run_agent_silently(agent: &mut Agent, input: &str) -> (String, usize) {
+    agent.run_once_with_steps(input).await
 } 
\ No newline at end of file
@@ -1,4 +1,6 @@
+use crate::role::Role;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents a team of agents that can work together
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +11,11 @@ pub struct Team {
     pub shared_memory: Option<String>, // Path to shared memory DB
     pub fsm: Option<FSMConfig>, // FSM-specific configuration
     pub dag: Option<DAGConfig>, // DAG-specific configuration
+    /// Named personas that a `TeamAgent.role` can reference by name; when a team
+    /// agent's `role` matches an entry here, that persona's system prompt is used
+    /// in place of the agent's free-text `instructions`
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
 }
 
 /// Builder for creating Team instances with a fluent API
@@ -19,6 +26,7 @@ pub struct TeamBuilder {
     shared_memory: Option<String>,
     fsm: Option<FSMConfig>,
     dag: Option<DAGConfig>,
+    roles: HashMap<String, Role>,
 }
 
 impl TeamBuilder {
@@ -31,6 +39,7 @@ impl TeamBuilder {
             shared_memory: None,
             fsm: None,
             dag: None,
+            roles: HashMap::new(),
         }
     }
 
@@ -64,10 +73,16 @@ impl TeamBuilder {
         self
     }
 
+    /// Register a persona that team agents can select via their `role` field
+    pub fn with_role(mut self, name: impl Into<String>, role: Role) -> Self {
+        self.roles.insert(name.into(), role);
+        self
+    }
+
     /// Build the Team
     pub fn build(self) -> Team {
         let workflow = self.workflow.unwrap_or(TeamWorkflow::RoundRobin);
-        
+
         Team {
             name: self.name,
             agents: self.agents,
@@ -75,6 +90,7 @@ impl TeamBuilder {
             shared_memory: self.shared_memory,
             fsm: self.fsm,
             dag: self.dag,
+            roles: self.roles,
         }
     }
 }
@@ -85,6 +101,16 @@ pub struct FSMConfig {
     pub states: Vec<String>,
     pub initial_state: String,
     pub transitions: Vec<StateTransition>,
+    /// Upper bound on state transitions before the FSM aborts a run that
+    /// keeps cycling instead of reaching a terminal state; defaults to
+    /// `states.len() * 2` when unset
+    #[serde(default)]
+    pub max_steps: Option<usize>,
+    /// Which `TeamAgent.name` is responsible for each state; a state missing
+    /// from this map has no agent to run it, see `team::dispatcher::execute_fsm`.
+    /// Defaults to empty for configs predating this field.
+    #[serde(default)]
+    pub agent_map: HashMap<String, String>,
 }
 
 /// DAG-specific configuration
@@ -117,6 +143,8 @@ pub enum TeamWorkflow {
         states: Vec<String>,
         transitions: Vec<StateTransition>,
         initial_state: String,
+        #[serde(default)]
+        agent_map: HashMap<String, String>,
     },
     /// Directed Acyclic Graph workflow
     DAG {
@@ -135,6 +163,7 @@ impl From<String> for TeamWorkflow {
                 states: vec![],
                 transitions: vec![],
                 initial_state: "start".to_string(),
+                agent_map: HashMap::new(),
             },
             "dag" => TeamWorkflow::DAG {
                 nodes: vec![],
@@ -149,7 +178,12 @@ impl From<String> for TeamWorkflow {
 pub struct StateTransition {
     pub from: String,
     pub to: String,
-    pub condition: String, // Simple condition like "task_complete" or "error"
+    /// Either one of `condition_matches`' built-in vocabulary ("task_complete",
+    /// "error", "needs_revision", "contains:<substr>") or an arbitrary free-text
+    /// label (e.g. "customer_satisfied"); labels outside the built-in vocabulary
+    /// are resolved by asking the model which label best matches the agent's
+    /// output, see `team::dispatcher::determine_next_state`.
+    pub condition: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -175,6 +209,7 @@ impl Team {
             shared_memory: None,
             fsm: None,
             dag: None,
+            roles: HashMap::new(),
         }
     }
 
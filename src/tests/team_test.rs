@@ -41,6 +41,7 @@ mod tests {
             states: vec!["start".to_string(), "process".to_string(), "end".to_string()],
             transitions: vec![],
             initial_state: "start".to_string(),
+            agent_map: std::collections::HashMap::new(),
         };
         
         // Test DAG
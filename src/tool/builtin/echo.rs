@@ -1,3 +1,5 @@
+use crate::error::HelixorError;
+use crate::tool::tool_traits::extract_arg;
 use crate::tool::Tool;
 
 pub struct EchoTool;
@@ -14,7 +16,9 @@ impl Tool for EchoTool {
         "echo"
     }
 
-    async fn call(&self, input: &str) -> String {
-        format!("Echo: {}", input)
+    // Uses the default `schema()` (a single "input" string parameter)
+
+    async fn call(&self, input: &str) -> Result<String, HelixorError> {
+        Ok(format!("Echo: {}", extract_arg(input, "input")))
     }
 }
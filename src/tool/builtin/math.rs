@@ -1,6 +1,8 @@
-use crate::tool::tool_traits::Tool;
+use crate::error::HelixorError;
+use crate::tool::tool_traits::{extract_arg, Tool};
 use async_trait::async_trait;
 use meval;
+use serde_json::{json, Value};
 
 pub struct MathTool;
 
@@ -16,10 +18,23 @@ impl Tool for MathTool {
         "math"
     }
 
-    async fn call(&self, input: &str) -> String {
-        match meval::eval_str(input) {
-            Ok(result) => format!("🧮 Result: {}", result),
-            Err(e) => format!("❌ Math error: {}", e),
-        }
+    fn schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "expression": {
+                    "type": "string",
+                    "description": "An arithmetic expression to evaluate, e.g. '2 * (3 + 4)'"
+                }
+            },
+            "required": ["expression"]
+        })
+    }
+
+    async fn call(&self, input: &str) -> Result<String, HelixorError> {
+        let expression = extract_arg(input, "expression");
+        meval::eval_str(&expression)
+            .map(|result| format!("🧮 Result: {}", result))
+            .map_err(|e| HelixorError::ToolFailure { tool: self.name().to_string(), msg: e.to_string() })
     }
 }
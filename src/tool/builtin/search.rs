@@ -1,7 +1,9 @@
-use crate::tool::tool_traits::Tool;
+use crate::error::HelixorError;
+use crate::tool::tool_traits::{extract_arg, Tool};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use serde_json::{json, Value};
 
 pub struct SearchTool;
 
@@ -31,37 +33,47 @@ impl Tool for SearchTool {
         "search"
     }
 
-    async fn call(&self, input: &str) -> String {
-        let query = input.trim();
+    fn schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search query to look up"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn call(&self, input: &str) -> Result<String, HelixorError> {
+        let query = extract_arg(input, "query");
+        let query = query.trim();
         let url = format!(
             "https://api.duckduckgo.com/?q={}&format=json&no_redirect=1&no_html=1",
             urlencoding::encode(query)
         );
 
+        let tool_failure = |msg: String| HelixorError::ToolFailure { tool: self.name().to_string(), msg };
+
         let client = Client::new();
-        let response = match client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(err) => {
-                return format!("❌ Failed to send request: {}", err);
-            }
-        };
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|err| tool_failure(format!("failed to send request: {}", err)))?;
 
-        let parsed: Result<DuckDuckGoResponse, _> = response.json().await;
-        match parsed {
-            Ok(data) => {
-                if !data.abstract_text.is_empty() {
-                    format!("🔎 {}", data.abstract_text)
-                } else if let Some(related) = data
-                    .related_topics
-                    .into_iter()
-                    .find_map(|topic| topic.text)
-                {
-                    format!("🔎 Related: {}", related)
-                } else {
-                    "🤷 No relevant result found.".to_string()
-                }
-            }
-            Err(err) => format!("❌ Failed to parse JSON: {}", err),
+        let data: DuckDuckGoResponse = response
+            .json()
+            .await
+            .map_err(|err| tool_failure(format!("failed to parse JSON: {}", err)))?;
+
+        if !data.abstract_text.is_empty() {
+            Ok(format!("🔎 {}", data.abstract_text))
+        } else if let Some(related) = data.related_topics.into_iter().find_map(|topic| topic.text) {
+            Ok(format!("🔎 Related: {}", related))
+        } else {
+            Ok("🤷 No relevant result found.".to_string())
         }
     }
 }
@@ -1,11 +1,50 @@
+use crate::error::HelixorError;
 use async_trait::async_trait;
+use serde_json::{json, Value};
 
 /// Trait that all tools must implement
 #[async_trait]
 pub trait Tool: Send + Sync {
-    /// Unique tool name (used in tool_call JSON)
+    /// Unique tool name (used in tool_call JSON and sent as the function name)
     fn name(&self) -> &str;
 
-    /// The logic to execute the tool
-    async fn call(&self, input: &str) -> String;
+    /// JSON-Schema description of this tool's parameters, sent to the model as part
+    /// of the provider's native `tools` array so it knows how to call this tool.
+    /// Tools that only accept a single free-text argument can return the default,
+    /// which describes one required string parameter named `input`.
+    fn schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "input": {
+                    "type": "string",
+                    "description": "Argument to pass to the tool"
+                }
+            },
+            "required": ["input"]
+        })
+    }
+
+    /// The logic to execute the tool. `input` is the raw arguments payload the model
+    /// sent back for this call — typically a JSON object matching `schema()`. Errors
+    /// are returned as a typed `HelixorError::ToolFailure` rather than baked into the
+    /// output string, so callers can branch on failure instead of parsing text.
+    async fn call(&self, input: &str) -> Result<String, HelixorError>;
+}
+
+/// Pull a single named argument out of a tool call's raw arguments payload. Accepts
+/// either a JSON object (the native function-calling case, e.g. `{"expression": "2+2"}`)
+/// or a bare string (the legacy JSON-in-text case), so existing tools keep working
+/// with either calling convention.
+pub fn extract_arg(raw: &str, key: &str) -> String {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Object(map)) => map
+            .get(key)
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_else(|| raw.to_string()),
+        _ => raw.to_string(),
+    }
 }
@@ -1,11 +1,72 @@
+use crate::agent::Agent;
+use crate::error::HelixorError;
 use crate::workflow::{Workflow, WorkflowState, WorkflowType, WorkflowStep};
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 /// Executes workflows and manages their lifecycle
 pub struct WorkflowRunner {
-    workflows: HashMap<String, Box<dyn Workflow>>,
+    /// `Arc` (rather than `Box`) so `tick` can clone a workflow out from under
+    /// a briefly-held lock and run it without holding `Mutex<WorkflowRunner>`
+    /// for the duration of the run - `Workflow::execute` only needs `&self`.
+    workflows: HashMap<String, Arc<dyn Workflow>>,
     active_states: HashMap<String, WorkflowState>,
+    schedules: HashMap<String, ScheduleEntry>,
+    /// Tracks which schedule ids currently have a run in flight, so `tick`
+    /// doesn't re-spawn a `Schedule::Every` entry whose previous execution
+    /// (an LLM-backed workflow can easily outlast the 1s tick interval)
+    /// hasn't finished yet - see `run_scheduled_entry`.
+    running: HashMap<String, bool>,
+}
+
+/// How often a scheduled workflow re-runs
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Re-run on a fixed interval, starting immediately, indefinitely
+    Every(Duration),
+    /// Run exactly once at a specific time, then deactivate
+    At(DateTime<Utc>),
+}
+
+impl Schedule {
+    fn first_run(&self) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(_) => Utc::now(),
+            Schedule::At(at) => *at,
+        }
+    }
+
+    /// The next run time after `now`, or `None` if this schedule is one-shot
+    /// and should be dropped once it fires
+    fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(interval) => chrono::Duration::from_std(*interval).ok().map(|d| now + d),
+            Schedule::At(_) => None,
+        }
+    }
+}
+
+struct ScheduleEntry {
+    workflow_name: String,
+    input: String,
+    schedule: Schedule,
+    next_run: DateTime<Utc>,
+    paused: bool,
+    last_workflow_id: Option<String>,
+}
+
+/// Point-in-time metadata about a registered schedule entry
+#[derive(Debug, Clone)]
+pub struct ScheduleStatus {
+    pub schedule_id: String,
+    pub workflow_name: String,
+    pub next_run: DateTime<Utc>,
+    pub paused: bool,
+    pub last_workflow_id: Option<String>,
 }
 
 impl WorkflowRunner {
@@ -13,40 +74,112 @@ impl WorkflowRunner {
         Self {
             workflows: HashMap::new(),
             active_states: HashMap::new(),
+            schedules: HashMap::new(),
+            running: HashMap::new(),
         }
     }
-    
+
     /// Register a workflow
     pub fn register_workflow(&mut self, name: &str, workflow: Box<dyn Workflow>) {
-        self.workflows.insert(name.to_string(), workflow);
+        self.workflows.insert(name.to_string(), Arc::from(workflow));
     }
-    
-    /// Execute a workflow by name
-    pub async fn execute_workflow(
+
+    /// Execute a workflow by name, returning its generated workflow id
+    /// alongside its output, so `execute_workflow` can record the id on its
+    /// tracing span
+    async fn execute_workflow_tracked(
         &mut self,
         workflow_name: &str,
         input: &str,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<(String, String), HelixorError> {
         let workflow = self.workflows.get(workflow_name)
-            .ok_or_else(|| format!("Workflow '{}' not found", workflow_name))?;
-        
+            .ok_or_else(|| HelixorError::WorkflowNotFound(workflow_name.to_string()))?;
+
         let workflow_id = Uuid::new_v4().to_string();
         let mut state = WorkflowState::new(workflow_id.clone());
-        
-        println!("🚀 Executing workflow: {} (ID: {})", workflow_name, workflow_id);
-        
+
+        tracing::info!(workflow_id = %workflow_id, "executing workflow");
+
         // Store active state
         self.active_states.insert(workflow_id.clone(), state.clone());
-        
+
         // Execute the workflow
         let result = workflow.execute(input, &mut state).await?;
-        
+
+        // Replay this run's per-step timing as structured events before the
+        // state is retired, so it's queryable even if nothing held a live span
+        state.emit_trace_events();
+
         // Update state with final results
-        self.active_states.insert(workflow_id, state);
-        
-        Ok(result)
+        self.active_states.insert(workflow_id.clone(), state);
+
+        Ok((workflow_id, result))
     }
-    
+
+    /// Execute a workflow by name
+    #[tracing::instrument(skip(self, input), fields(workflow_name = %workflow_name, workflow_id = tracing::field::Empty))]
+    pub async fn execute_workflow(
+        &mut self,
+        workflow_name: &str,
+        input: &str,
+    ) -> Result<String, HelixorError> {
+        let (workflow_id, output) = self.execute_workflow_tracked(workflow_name, input).await?;
+        tracing::Span::current().record("workflow_id", workflow_id.as_str());
+        Ok(output)
+    }
+
+    /// Register `workflow_name` to run on `schedule`, returning an id that
+    /// `pause_schedule`/`resume_schedule`/`cancel_schedule` use to address it.
+    /// Actually ticking schedules requires `spawn_scheduler` to be running
+    /// against an `Arc<Mutex<WorkflowRunner>>` that owns this entry.
+    pub fn schedule_workflow(&mut self, workflow_name: &str, input: &str, schedule: Schedule) -> String {
+        let schedule_id = Uuid::new_v4().to_string();
+        let next_run = schedule.first_run();
+
+        self.schedules.insert(
+            schedule_id.clone(),
+            ScheduleEntry {
+                workflow_name: workflow_name.to_string(),
+                input: input.to_string(),
+                schedule,
+                next_run,
+                paused: false,
+                last_workflow_id: None,
+            },
+        );
+
+        schedule_id
+    }
+
+    /// Stop running a schedule's future ticks without forgetting it
+    pub fn pause_schedule(&mut self, schedule_id: &str) -> bool {
+        self.schedules.get_mut(schedule_id).map(|entry| entry.paused = true).is_some()
+    }
+
+    /// Resume a schedule paused by `pause_schedule`
+    pub fn resume_schedule(&mut self, schedule_id: &str) -> bool {
+        self.schedules.get_mut(schedule_id).map(|entry| entry.paused = false).is_some()
+    }
+
+    /// Remove a schedule entirely; already-running ticks are unaffected
+    pub fn cancel_schedule(&mut self, schedule_id: &str) -> bool {
+        self.schedules.remove(schedule_id).is_some()
+    }
+
+    /// Metadata for every registered schedule entry, active or paused
+    pub fn get_schedules(&self) -> Vec<ScheduleStatus> {
+        self.schedules
+            .iter()
+            .map(|(id, entry)| ScheduleStatus {
+                schedule_id: id.clone(),
+                workflow_name: entry.workflow_name.clone(),
+                next_run: entry.next_run,
+                paused: entry.paused,
+                last_workflow_id: entry.last_workflow_id.clone(),
+            })
+            .collect()
+    }
+
     /// Get status of active workflows
     pub fn get_active_workflows(&self) -> Vec<WorkflowStatus> {
         self.active_states
@@ -68,6 +201,20 @@ impl WorkflowRunner {
         let workflow = ChainOfThoughtWorkflow::new(steps);
         self.register_workflow(name, Box::new(workflow));
     }
+
+    /// Create a DAG workflow: steps run concurrently once their `depends_on`
+    /// dependencies complete, instead of strictly in declared order
+    pub fn create_dag_workflow(
+        &mut self,
+        name: &str,
+        steps: Vec<WorkflowStep>,
+        agents: HashMap<String, Arc<Mutex<Agent>>>,
+    ) {
+        use crate::workflow::workflow_trait::DagWorkflow;
+
+        let workflow = DagWorkflow::new(steps, agents);
+        self.register_workflow(name, Box::new(workflow));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -78,3 +225,108 @@ pub struct WorkflowStatus {
     pub execution_time: u64,
     pub is_complete: bool,
 }
+
+/// Run every due, non-paused, not-already-running schedule entry once and
+/// advance its next-run time; one-shot `Schedule::At` entries are dropped
+/// once they fire
+async fn tick(runner: &Arc<Mutex<WorkflowRunner>>) {
+    let due: Vec<(String, String, String)> = {
+        let mut runner = runner.lock().await;
+        let now = Utc::now();
+        let due_ids: Vec<String> = runner
+            .schedules
+            .iter()
+            .filter(|(id, entry)| {
+                !entry.paused && entry.next_run <= now && !*runner.running.get(*id).unwrap_or(&false)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        // Mark each selected entry as running before releasing the lock so
+        // the next tick (at most 1s away) can't select it again while its
+        // workflow - possibly an LLM call taking far longer than 1s - is
+        // still executing.
+        due_ids
+            .into_iter()
+            .map(|id| {
+                runner.running.insert(id.clone(), true);
+                let entry = &runner.schedules[&id];
+                (id, entry.workflow_name.clone(), entry.input.clone())
+            })
+            .collect()
+    };
+
+    // Each due entry runs in its own task so a slow workflow (an LLM call can
+    // take seconds) doesn't hold up the others, and so callers of
+    // `execute_workflow`/`pause_schedule`/`cancel_schedule`/`get_schedules`
+    // against this same `Arc<Mutex<WorkflowRunner>>` only ever block for the
+    // brief re-locks below, never for a whole run.
+    for (schedule_id, workflow_name, input) in due {
+        let runner = runner.clone();
+        tokio::spawn(run_scheduled_entry(runner, schedule_id, workflow_name, input));
+    }
+}
+
+/// Runs one due schedule entry's workflow and records the outcome, re-locking
+/// `runner` only briefly before and after the (un-held-lock) `execute` await
+async fn run_scheduled_entry(
+    runner: Arc<Mutex<WorkflowRunner>>,
+    schedule_id: String,
+    workflow_name: String,
+    input: String,
+) {
+    let workflow = {
+        let runner = runner.lock().await;
+        runner.workflows.get(&workflow_name).cloned()
+    };
+    let Some(workflow) = workflow else {
+        tracing::warn!(schedule_id = %schedule_id, workflow_name = %workflow_name, "no workflow registered for scheduled entry");
+        return;
+    };
+
+    let workflow_id = Uuid::new_v4().to_string();
+    let mut state = WorkflowState::new(workflow_id.clone());
+    tracing::info!(workflow_id = %workflow_id, "executing workflow");
+
+    {
+        let mut runner = runner.lock().await;
+        runner.active_states.insert(workflow_id.clone(), state.clone());
+    }
+
+    let result = workflow.execute(&input, &mut state).await;
+    if result.is_ok() {
+        state.emit_trace_events();
+    }
+
+    let mut runner = runner.lock().await;
+    runner.active_states.insert(workflow_id.clone(), state);
+
+    runner.running.remove(&schedule_id);
+
+    let Some(entry) = runner.schedules.get_mut(&schedule_id) else {
+        return; // cancelled while this entry's run was in flight
+    };
+
+    match result {
+        Ok(_output) => entry.last_workflow_id = Some(workflow_id),
+        Err(e) => tracing::warn!(schedule_id = %schedule_id, error = %e, "scheduled workflow run failed"),
+    }
+
+    match entry.schedule.next_after(Utc::now()) {
+        Some(next_run) => entry.next_run = next_run,
+        None => {
+            runner.schedules.remove(&schedule_id);
+        }
+    }
+}
+
+/// Spawn a background task that polls for due schedule entries once a second
+/// and runs them; hold onto the returned handle to cancel the loop
+pub fn spawn_scheduler(runner: Arc<Mutex<WorkflowRunner>>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tick(&runner).await;
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    })
+}
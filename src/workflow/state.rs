@@ -82,4 +82,21 @@ impl WorkflowState {
             .as_secs();
         current_time - self.start_time
     }
+
+    /// Re-emit every recorded `StepResult` as a structured tracing event, so a
+    /// completed run can be replayed from its persisted state (e.g. after a
+    /// scheduler tick) instead of only being observable while it's live.
+    pub fn emit_trace_events(&self) {
+        for step in &self.step_results {
+            tracing::info!(
+                workflow_id = %self.workflow_id,
+                step_index = step.step_index,
+                agent = %step.agent,
+                input_len = step.input.len(),
+                output_len = step.output.len(),
+                duration_ms = step.duration_ms,
+                "workflow step"
+            );
+        }
+    }
 }
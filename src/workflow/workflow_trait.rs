@@ -1,12 +1,21 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+use crate::agent::Agent;
+use crate::error::HelixorError;
+use crate::workflow::state::StepResult;
 use crate::workflow::WorkflowState;
 
 /// Trait for different workflow implementations
 #[async_trait]
 pub trait Workflow: Send + Sync {
     /// Execute the workflow with given input
-    async fn execute(&self, input: &str, state: &mut WorkflowState) -> Result<String, Box<dyn std::error::Error>>;
+    async fn execute(&self, input: &str, state: &mut WorkflowState) -> Result<String, HelixorError>;
     
     /// Get workflow metadata
     fn metadata(&self) -> WorkflowMetadata;
@@ -38,6 +47,11 @@ pub struct WorkflowStep {
     pub description: String,
     pub agent: String,
     pub tools: Vec<String>,
+    /// Names of steps that must complete before this one is scheduled; empty
+    /// means this step is ready immediately. Only consulted by `DagWorkflow` —
+    /// `ChainOfThoughtWorkflow` runs steps in declared order regardless.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 
 impl ChainOfThoughtWorkflow {
@@ -48,13 +62,14 @@ impl ChainOfThoughtWorkflow {
 
 #[async_trait]
 impl Workflow for ChainOfThoughtWorkflow {
-    async fn execute(&self, input: &str, state: &mut WorkflowState) -> Result<String, Box<dyn std::error::Error>> {
+    #[tracing::instrument(skip(self, input, state), fields(workflow_id = %state.workflow_id))]
+    async fn execute(&self, input: &str, state: &mut WorkflowState) -> Result<String, HelixorError> {
         let mut current_input = input.to_string();
         let mut results = Vec::new();
-        
+
         for (i, step) in self.steps.iter().enumerate() {
-            println!("🔗 CoT Step {}: {} ({})", i + 1, step.name, step.agent);
-            
+            tracing::info!(step = i + 1, agent = %step.agent, name = %step.name, "executing CoT step");
+
             // Store step in state
             state.set_current_step(i);
             state.set_current_agent(&step.agent);
@@ -77,4 +92,170 @@ impl Workflow for ChainOfThoughtWorkflow {
             workflow_type: WorkflowType::ChainOfThought,
         }
     }
-} 
\ No newline at end of file
+}
+
+/// DAG workflow implementation: steps declare their dependencies via
+/// `WorkflowStep.depends_on` and run concurrently, wave by wave, once those
+/// dependencies complete (a step whose last dependency finishes mid-wave starts
+/// with the next wave), instead of the fixed sequential order
+/// `ChainOfThoughtWorkflow` uses.
+pub struct DagWorkflow {
+    steps: Vec<WorkflowStep>,
+    /// Agents keyed by `WorkflowStep.agent`, resolved once up front so `execute`
+    /// can hand each ready step straight to the agent that should run it
+    agents: HashMap<String, Arc<Mutex<Agent>>>,
+}
+
+impl DagWorkflow {
+    pub fn new(steps: Vec<WorkflowStep>, agents: HashMap<String, Arc<Mutex<Agent>>>) -> Self {
+        Self { steps, agents }
+    }
+}
+
+#[async_trait]
+impl Workflow for DagWorkflow {
+    #[tracing::instrument(skip(self, input, state), fields(workflow_id = %state.workflow_id))]
+    async fn execute(&self, input: &str, state: &mut WorkflowState) -> Result<String, HelixorError> {
+        let steps_by_name: HashMap<&str, &WorkflowStep> =
+            self.steps.iter().map(|s| (s.name.as_str(), s)).collect();
+
+        let mut in_degree: HashMap<String, usize> = HashMap::new();
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        for step in &self.steps {
+            in_degree.entry(step.name.clone()).or_insert(0);
+            for dep in &step.depends_on {
+                *in_degree.entry(step.name.clone()).or_insert(0) += 1;
+                dependents.entry(dep.clone()).or_default().push(step.name.clone());
+            }
+        }
+
+        let mut ready: Vec<String> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(name, _)| name.clone())
+            .collect();
+        let mut scheduled: HashSet<String> = HashSet::new();
+        let mut outputs: HashMap<String, String> = HashMap::new();
+
+        while !ready.is_empty() {
+            let mut join_set = JoinSet::new();
+
+            for name in ready.drain(..) {
+                scheduled.insert(name.clone());
+                let step = (*steps_by_name
+                    .get(name.as_str())
+                    .ok_or_else(|| format!("DAG references unknown step '{}'", name))?)
+                .clone();
+                let agent = self
+                    .agents
+                    .get(&step.agent)
+                    .cloned()
+                    .ok_or_else(|| format!("no agent registered for step '{}' (agent '{}')", step.name, step.agent))?;
+
+                // A step's input is the concatenation of its dependencies' outputs;
+                // a root step (no dependencies) gets the workflow's own input
+                let step_input = if step.depends_on.is_empty() {
+                    input.to_string()
+                } else {
+                    step.depends_on
+                        .iter()
+                        .filter_map(|dep| outputs.get(dep).cloned())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+
+                let step_span = tracing::info_span!(
+                    "dag_step",
+                    step = %step.name,
+                    agent = %step.agent,
+                    input_len = step_input.len(),
+                    output_len = tracing::field::Empty,
+                    duration_ms = tracing::field::Empty,
+                );
+
+                join_set.spawn(
+                    async move {
+                        let started = SystemTime::now();
+                        let mut agent = agent.lock().await;
+                        let output = agent.run_once(&step_input).await;
+                        let duration_ms = SystemTime::now()
+                            .duration_since(started)
+                            .unwrap_or_default()
+                            .as_millis() as u64;
+                        let span = tracing::Span::current();
+                        span.record("output_len", output.len());
+                        span.record("duration_ms", duration_ms);
+                        (step, step_input, output, duration_ms)
+                    }
+                    .instrument(step_span),
+                );
+            }
+
+            // Agent::run_once never itself returns an error (failures come back as
+            // natural-language text), so the only failure mode we can detect here is
+            // a panicked step task; on one, cancel the rest of this wave and
+            // surface it, matching the "first failing step" contract as closely as
+            // the underlying Agent API allows.
+            while let Some(joined) = join_set.join_next().await {
+                let (step, step_input, output, duration_ms) = match joined {
+                    Ok(result) => result,
+                    Err(join_err) => {
+                        join_set.shutdown().await;
+                        return Err(format!("step task panicked: {join_err}").into());
+                    }
+                };
+
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                state.add_step_result(StepResult {
+                    step_index: state.step_results.len(),
+                    agent: step.agent.clone(),
+                    input: step_input,
+                    output: output.clone(),
+                    timestamp,
+                    duration_ms,
+                });
+                state.set_variable(&step.name, &output);
+                outputs.insert(step.name.clone(), output);
+
+                if let Some(deps) = dependents.get(&step.name) {
+                    for dependent in deps {
+                        let degree = in_degree.get_mut(dependent).expect("dependent tracked in in_degree");
+                        *degree -= 1;
+                        if *degree == 0 {
+                            ready.push(dependent.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        if scheduled.len() != self.steps.len() {
+            let unscheduled: Vec<&str> = self
+                .steps
+                .iter()
+                .map(|s| s.name.as_str())
+                .filter(|name| !scheduled.contains(*name))
+                .collect();
+            return Err(format!("DAG has a cycle; steps never scheduled: {}", unscheduled.join(", ")).into());
+        }
+
+        let mut ordered_outputs: Vec<&WorkflowStep> = self.steps.iter().collect();
+        ordered_outputs.sort_by_key(|s| &s.name);
+        Ok(ordered_outputs
+            .into_iter()
+            .filter_map(|step| outputs.get(&step.name).map(|output| format!("{}: {}", step.name, output)))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    fn metadata(&self) -> WorkflowMetadata {
+        WorkflowMetadata {
+            name: "DAG".to_string(),
+            description: "Dependency-ordered, concurrently scheduled workflow".to_string(),
+            workflow_type: WorkflowType::DAG,
+        }
+    }
+}
\ No newline at end of file